@@ -0,0 +1,130 @@
+use crate::{app::InputMode, import::ImportMode, sort::SortMode, Status};
+
+/// Something the user asked the app to do. Key and mouse handlers translate events into
+/// actions and `App::dispatch` applies them, so tests and scripts can drive the app the
+/// same way a user does.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    Quit,
+
+    /// Selects the next entry, moving on to the next column after the last one.
+    SelectNext,
+    SelectPrev,
+    SelectNextColumn,
+    SelectPrevColumn,
+    /// Selects a row (0-based) of the selected column, or its last row for `None`.
+    SelectRow(Option<usize>),
+    /// Moves the selection this many rows down (up if negative) within its column.
+    MoveSelection(isize),
+    /// Selects the entry at this index, like a mouse click.
+    Select(usize),
+    /// Scrolls a board column this many rows down (up if negative), keeping the selection
+    /// in view.
+    ScrollColumn(Status, isize),
+    /// Scrolls a column of the Dropped list, like `ScrollColumn`.
+    ScrollDropped(usize, isize),
+    /// Selects the first shown entry with this status, like clicking its tab.
+    SelectColumn(Status),
+    /// Picks or unpicks the selected entry for bulk actions and moves to the next row.
+    ToggleSelected,
+    /// Picks every entry from the last toggled one to the entry at this index.
+    SelectRange(usize),
+
+    ToggleHelp,
+    ToggleFullTitle,
+    ToggleDetails,
+    ToggleStats,
+    OpenEpisodes,
+    OpenDropped,
+    OpenActivity,
+    /// Moves the day picked in the activity calendar by this many days.
+    MoveActivityDay(i64),
+    /// Closes the current popup, prompt or input without applying it.
+    Cancel,
+
+    AddEntry(String),
+    EditTitle(String),
+    /// Counters of the selected entry, changed by this many steps with a single save.
+    NextEpisode(u32),
+    PrevEpisode(u32),
+    NextSeason(u32),
+    IncrementWatched(u32),
+    DecrementWatched(u32),
+    /// Sets the season and/or episode of the selected entry.
+    SetPosition(Option<u32>, Option<u32>),
+    SetMaxEpisodes(u32),
+    SetRuntime(u32),
+    SetSeasonRuntime(u32),
+    /// `YYYY-MM-DD`, or empty to clear.
+    SetAirDate(String),
+    /// `YYYY-MM-DD HH:MM`.
+    AddWatchSession(String),
+    ClearWatchSessions,
+    /// Moves the selected entry, or the picked entries as a block, this many rows up.
+    MoveUp(u32),
+    MoveDown(u32),
+    /// Moves the selected or picked entries to a status.
+    MoveTo(Status),
+    Reactivate,
+    /// Asks to drop the selected or picked entries, or delete them in the Dropped list.
+    RequestRemove,
+    Tag(String),
+    Untag(String),
+    RequestRemoveAllDropped,
+    /// Answers yes to the pending remove prompt.
+    ConfirmRemove,
+
+    Export(String),
+    LoadImport(String),
+    ApplyImport(ImportMode),
+    ScrollImportPreview(i16),
+    /// Scrolls the help popup by this many rows.
+    ScrollHelp(i16),
+
+    /// Opens the input bar or a popup prompt for `mode`, prefilled where it makes sense.
+    StartInput(InputMode),
+    /// Types a character at the cursor.
+    InsertChar(char),
+    DeleteBackward,
+    DeleteForward,
+    CursorLeft,
+    CursorRight,
+    CursorHome,
+    CursorEnd,
+    /// Moves the cursor to the start of the previous word.
+    CursorWordLeft,
+    /// Moves the cursor to the end of the next word.
+    CursorWordRight,
+    DeleteWordBackward,
+    DeleteWordForward,
+    /// Cuts everything before the cursor into the kill buffer.
+    KillToStart,
+    /// Cuts everything after the cursor into the kill buffer.
+    KillToEnd,
+    /// Inserts the text cut last.
+    Yank,
+    /// Inserts pasted text at the cursor as it is.
+    Paste(String),
+    /// Moves the cursor to a grapheme position.
+    SetCursor(usize),
+    /// Applies the text typed for the current input mode.
+    SubmitInput,
+
+    /// Sorts the selected column by this mode, or by the next one for `None`.
+    SetSort(Option<SortMode>),
+    /// Shows only entries whose titles fuzzy match this text, or everything if it's empty.
+    SetFilter(String),
+
+    /// Runs a command palette line, like `move completed`.
+    RunCommand(String),
+    /// Completes the command being typed, cycling through the candidates on repeats.
+    CompleteCommand,
+    /// Recalls the previous command from the history.
+    HistoryPrev,
+    HistoryNext,
+
+    /// Starts dragging the entry at this index.
+    StartDrag(usize),
+    /// Drops the dragged entry on a column, or cancels the drag for `None`.
+    DropDragged(Option<Status>),
+}
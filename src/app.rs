@@ -1,9 +1,25 @@
-use crate::{config::Config, database, Entry, Status};
+use crate::action::Action;
+use crate::command;
+use crate::consts::MAX_COMMAND_HISTORY;
+use crate::fuzzy;
+use crate::import::{ImportMode, ImportPreview};
+use crate::input::InputResult;
+use crate::keymap::{KeyChord, Keymap};
+use crate::scroll;
+use crate::sort::SortMode;
+use crate::stats;
+use crate::theme::Theme;
+use crate::{config::Config, database, formats, Entry, Status};
+use chrono::{Days, NaiveDate, NaiveDateTime, TimeDelta};
 use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
+use unicode_segmentation::UnicodeSegmentation;
 use uuid::Uuid;
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum InputMode {
     Normal,
     Editing,
@@ -13,6 +29,82 @@ pub enum InputMode {
     Dropped,
     ConfirmDeleteAllDropped,
     TotalEpisodes,
+    Runtime,
+    SeasonRuntime,
+    AirDate,
+    WatchSession,
+    ExportPath,
+    ImportPath,
+    ConfirmImport,
+    Command,
+    Filter,
+    Activity,
+}
+
+impl InputMode {
+    /// Modes that type numbers or dates into the Episodes Watched popup.
+    pub fn is_popup_input(&self) -> bool {
+        matches!(
+            self,
+            InputMode::MaxEpisodes
+                | InputMode::Runtime
+                | InputMode::SeasonRuntime
+                | InputMode::AirDate
+                | InputMode::WatchSession
+        )
+    }
+
+    /// Modes that type into the input bar below the board.
+    pub fn uses_input_bar(&self) -> bool {
+        matches!(
+            self,
+            InputMode::Adding
+                | InputMode::Editing
+                | InputMode::ExportPath
+                | InputMode::ImportPath
+                | InputMode::Command
+                | InputMode::Filter
+        )
+    }
+}
+
+/// Why `App::find_entry` couldn't pick a single entry.
+#[derive(Debug, PartialEq)]
+pub enum LookupError {
+    NotFound(String),
+    /// The query and every title it matches.
+    Ambiguous(String, Vec<String>),
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LookupError::NotFound(query) => write!(f, "No entry matches '{}'", query),
+            LookupError::Ambiguous(query, titles) => write!(
+                f,
+                "'{}' matches several entries: {}",
+                query,
+                titles.join(", ")
+            ),
+        }
+    }
+}
+
+/// Why `App::add_entry` didn't add an entry.
+#[derive(Debug, PartialEq)]
+pub enum AddError {
+    /// An entry with the title exists already.
+    Duplicate(String),
+    Storage(String),
+}
+
+impl fmt::Display for AddError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddError::Duplicate(title) => write!(f, "Entry with title '{}' already exists.", title),
+            AddError::Storage(e) => write!(f, "{}", e),
+        }
+    }
 }
 
 pub struct App {
@@ -23,21 +115,66 @@ pub struct App {
     pub layout: Vec<Rect>,
     pub column_layout: Vec<Rect>,
     pub dropped_column_layout: Vec<Rect>,
+    /// First visible row of each board column, kept between frames.
+    pub column_offsets: HashMap<Status, usize>,
+    /// First visible row of each column of the Dropped list.
+    pub dropped_offsets: [usize; 2],
     pub input: String,
     pub cursor_position: usize,
+    /// Text cut by the last kill in an input, put back by `Action::Yank`.
+    pub kill_buffer: String,
     pub input_mode: InputMode,
     pub show_help: bool,
     pub show_full_title: bool,
+    /// Whether the detail pane for the selected entry is shown next to or below the board.
+    pub show_details: bool,
+    pub show_stats: bool,
+    /// Day picked in the activity calendar.
+    pub activity_day: NaiveDate,
     pub show_dropped: bool,
     pub show_total_episodes_popup: bool,
     pub dropped_is_two_column: bool,
     pub config: Config,
     pub error: Option<String>,
     pub last_error_time: Option<Instant>,
+    pub info: Option<String>,
+    pub last_info_time: Option<Instant>,
+    pub pending_import: Option<ImportPreview>,
+    pub import_preview_scroll: u16,
+    pub help_scroll: u16,
+    /// Tab of each board column while the board shows one column at a time, empty otherwise.
+    pub tab_layout: Vec<Rect>,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    /// Keys of a binding that is still being typed, like the first `g` of `g g`.
+    pub pending_keys: Vec<KeyChord>,
+    /// Count prefix typed before an action, like the `5` of `5+`.
+    pub count: Option<u32>,
+    /// Command palette lines run this session, oldest first.
+    pub command_history: Vec<String>,
+    /// Position in `command_history` while browsing it with up/down.
+    pub history_index: Option<usize>,
+    /// What was typed before browsing the history, restored when browsing past the end.
+    pub history_draft: String,
+    /// Candidates offered by the last tab completion, cycled by repeated tabs.
+    pub completions: Vec<String>,
+    pub completion_index: Option<usize>,
+    /// Only entries whose titles fuzzy match this are shown on the board.
+    pub filter: String,
+    /// Selection from before filtering, restored when the filter is abandoned with esc.
+    pub filter_saved_selection: Option<usize>,
+    /// Sort mode of each board column; missing columns are sorted manually.
+    pub sort_modes: HashMap<Status, SortMode>,
+    /// Entries picked for a bulk action, by id.
+    pub selection: HashSet<Uuid>,
+    /// Where the last range selection started.
+    pub selection_anchor: Option<Uuid>,
 }
 
 impl App {
     pub fn new(entry: Vec<Entry>, config: Config) -> Self {
+        let theme = config.theme.resolve();
+        let keymap = Keymap::new(&config.keys).unwrap_or_default();
         let mut app = Self {
             entry,
             selected_index: 0,
@@ -46,36 +183,747 @@ impl App {
             layout: Vec::new(),
             column_layout: Vec::new(),
             dropped_column_layout: Vec::new(),
+            column_offsets: HashMap::new(),
+            dropped_offsets: [0; 2],
             input: String::new(),
             cursor_position: 0,
+            kill_buffer: String::new(),
             input_mode: InputMode::Normal,
             show_help: false,
             show_full_title: false,
+            show_details: false,
+            show_stats: false,
+            activity_day: database::now().date(),
             show_dropped: false,
             show_total_episodes_popup: false,
             dropped_is_two_column: false,
             config,
             error: None,
             last_error_time: None,
+            info: None,
+            last_info_time: None,
+            pending_import: None,
+            import_preview_scroll: 0,
+            help_scroll: 0,
+            tab_layout: Vec::new(),
+            theme,
+            keymap,
+            pending_keys: Vec::new(),
+            count: None,
+            command_history: Vec::new(),
+            history_index: None,
+            history_draft: String::new(),
+            completions: Vec::new(),
+            completion_index: None,
+            filter: String::new(),
+            filter_saved_selection: None,
+            sort_modes: HashMap::new(),
+            selection: HashSet::new(),
+            selection_anchor: None,
         };
         app.select_first_entry_in_normal_view();
         app
     }
 
-    pub fn add_entry(&mut self, title: String) {
-        match database::get_entry_by_title(&title, &self.config) {
-            Ok(Some(_)) => {
-                self.error = Some(format!("Entry with title '{}' already exists.", title));
+    /// Applies one user action. Every change made from the keyboard or mouse goes through
+    /// here.
+    pub fn dispatch(&mut self, action: Action) -> InputResult {
+        let navigation = action_is_navigation(&action);
+        if action != Action::CompleteCommand {
+            self.completions.clear();
+            self.completion_index = None;
+        }
+        match action {
+            Action::Quit => return InputResult::Quit,
+            Action::SelectNext if self.input_mode == InputMode::Dropped => {
+                self.select_next_dropped(1)
+            }
+            Action::SelectPrev if self.input_mode == InputMode::Dropped => {
+                self.select_next_dropped(-1)
+            }
+            Action::SelectNextColumn | Action::SelectPrevColumn
+                if self.input_mode == InputMode::Dropped =>
+            {
+                self.switch_dropped_column()
+            }
+            Action::SelectNext => self.next_entry(),
+            Action::SelectPrev => self.prev_entry(),
+            Action::SelectNextColumn => self.select_next_column(),
+            Action::SelectPrevColumn => self.select_prev_column(),
+            Action::SelectRow(row) if self.input_mode == InputMode::Dropped => {
+                self.select_dropped_row(row)
+            }
+            Action::MoveSelection(rows) if self.input_mode == InputMode::Dropped => {
+                self.select_next_dropped(rows)
+            }
+            Action::SelectRow(row) => self.select_row_in_column(row),
+            Action::MoveSelection(rows) => self.move_selection_in_column(rows),
+            Action::ScrollColumn(status, rows) => self.scroll_column(status, rows),
+            Action::ScrollDropped(col, rows) => self.scroll_dropped(col, rows),
+            Action::Select(index) if index < self.entry.len() => self.selected_index = index,
+            Action::Select(_) => {}
+            Action::SelectColumn(status) => {
+                if let Some((idx, _)) = self.get_entries_by_status(status).first() {
+                    self.selected_index = *idx;
+                }
+            }
+            Action::ToggleHelp => {
+                self.show_help = !self.show_help;
+                self.help_scroll = 0;
+            }
+            Action::ToggleFullTitle => self.show_full_title = !self.show_full_title,
+            Action::ToggleDetails => self.show_details = !self.show_details,
+            Action::ToggleStats => self.show_stats = !self.show_stats,
+            Action::OpenEpisodes => {
+                self.show_total_episodes_popup = true;
+                self.input_mode = InputMode::TotalEpisodes;
+            }
+            Action::OpenActivity => {
+                self.activity_day = database::now().date();
+                self.input_mode = InputMode::Activity;
+            }
+            Action::MoveActivityDay(days) => self.move_activity_day(days),
+            Action::OpenDropped => {
+                self.selection.clear();
+                self.show_dropped = true;
+                self.input_mode = InputMode::Dropped;
+                if let Some((idx, _)) = self.get_dropped_entries().first() {
+                    self.selected_index = *idx;
+                }
+            }
+            Action::Cancel => self.cancel(),
+            Action::AddEntry(title) => {
+                if let Err(e) = self.add_entry(title) {
+                    self.error = Some(e.to_string());
+                    self.last_error_time = Some(Instant::now());
+                }
+            }
+            Action::EditTitle(title) => self.edit_entry_title(title),
+            Action::NextEpisode(count) => self.next_episode(count),
+            Action::PrevEpisode(count) => self.prev_episode(count),
+            Action::NextSeason(count) => self.next_season(count),
+            Action::IncrementWatched(count) => self.increment_watched_episodes(count),
+            Action::DecrementWatched(count) => self.decrement_watched_episodes(count),
+            Action::SetPosition(season, episode) => self.set_position(season, episode),
+            Action::SetMaxEpisodes(value) => self.set_max_episodes(value),
+            Action::SetRuntime(minutes) => self.set_runtime(minutes),
+            Action::SetSeasonRuntime(minutes) => self.set_season_runtime(minutes),
+            Action::SetAirDate(date) => self.set_next_air_date(&date),
+            Action::AddWatchSession(session) => self.add_watch_session(&session),
+            Action::ClearWatchSessions => self.clear_watch_sessions(),
+            Action::MoveUp(count) => self.move_entry_up_in_column(count),
+            Action::MoveDown(count) => self.move_entry_down_in_column(count),
+            Action::MoveTo(status) => self.move_to(status),
+            Action::Reactivate => self.reactivate_entry(),
+            Action::RequestRemove if !self.entry.is_empty() => {
+                self.input_mode = InputMode::ConfirmDelete;
+            }
+            Action::RequestRemoveAllDropped if !self.get_dropped_entries().is_empty() => {
+                self.input_mode = InputMode::ConfirmDeleteAllDropped;
+            }
+            Action::RequestRemove | Action::RequestRemoveAllDropped => {}
+            Action::ConfirmRemove => {
+                match self.input_mode {
+                    InputMode::ConfirmDelete if self.show_dropped => self.force_remove_entry(),
+                    InputMode::ConfirmDelete => self.drop_entry(),
+                    InputMode::ConfirmDeleteAllDropped => self.force_remove_all_dropped_entries(),
+                    _ => return InputResult::Success,
+                }
+                self.input_mode = if self.show_dropped {
+                    InputMode::Dropped
+                } else {
+                    InputMode::Normal
+                };
+            }
+            Action::Export(path) => self.export_entries(&path),
+            Action::LoadImport(path) => {
+                self.input_mode = if self.load_import(&path) {
+                    InputMode::ConfirmImport
+                } else {
+                    InputMode::Normal
+                };
+                return InputResult::Success;
+            }
+            Action::ApplyImport(mode) => {
+                self.apply_import(mode);
+                self.input_mode = InputMode::Normal;
+            }
+            Action::ScrollImportPreview(rows) => {
+                self.import_preview_scroll = self.import_preview_scroll.saturating_add_signed(rows);
+                return InputResult::Success;
+            }
+            Action::ScrollHelp(rows) => {
+                self.help_scroll = self.help_scroll.saturating_add_signed(rows);
+                return InputResult::Success;
+            }
+            Action::StartInput(mode) => {
+                self.start_input(mode);
+                return InputResult::Success;
+            }
+            Action::InsertChar(c) => {
+                self.insert_char(c);
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::DeleteBackward => {
+                if self.cursor_position > 0 {
+                    self.cursor_position -= 1;
+                    self.remove_grapheme_at_cursor();
+                }
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::DeleteForward => {
+                self.remove_grapheme_at_cursor();
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::CursorLeft => {
+                self.set_cursor(self.cursor_position.saturating_sub(1));
+                return InputResult::Success;
+            }
+            Action::CursorRight => {
+                self.set_cursor(self.cursor_position + 1);
+                return InputResult::Success;
+            }
+            Action::SetCursor(position) => {
+                self.set_cursor(position);
+                return InputResult::Success;
+            }
+            Action::CursorHome => {
+                self.set_cursor(0);
+                return InputResult::Success;
+            }
+            Action::CursorEnd => {
+                self.set_cursor(usize::MAX);
+                return InputResult::Success;
+            }
+            Action::CursorWordLeft => {
+                self.set_cursor(self.word_start_before(self.cursor_position));
+                return InputResult::Success;
+            }
+            Action::CursorWordRight => {
+                self.set_cursor(self.word_end_after(self.cursor_position));
+                return InputResult::Success;
+            }
+            Action::DeleteWordBackward
+            | Action::DeleteWordForward
+            | Action::KillToStart
+            | Action::KillToEnd => {
+                let cursor = self.cursor_position;
+                let (start, end) = match action {
+                    Action::DeleteWordBackward => (self.word_start_before(cursor), cursor),
+                    Action::DeleteWordForward => (cursor, self.word_end_after(cursor)),
+                    Action::KillToStart => (0, cursor),
+                    _ => (cursor, usize::MAX),
+                };
+                self.kill(start, end);
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::Yank => {
+                self.paste(&self.kill_buffer.clone());
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::Paste(text) => {
+                self.paste(&text);
+                self.filter_as_typed();
+                return InputResult::Success;
+            }
+            Action::SubmitInput => return self.submit_input(),
+            Action::SetSort(mode) => self.set_sort_mode(mode),
+            Action::ToggleSelected => self.toggle_selected(),
+            Action::SelectRange(index) => self.select_range(index),
+            Action::Tag(tag) => self.tag_entries(&tag, true),
+            Action::Untag(tag) => self.tag_entries(&tag, false),
+            Action::SetFilter(query) => {
+                if query.is_empty() {
+                    self.filter_saved_selection = None;
+                } else if self.filter.is_empty() {
+                    self.filter_saved_selection = Some(self.selected_index);
+                }
+                self.set_filter(query);
+            }
+            Action::RunCommand(line) => return self.run_command(&line),
+            Action::CompleteCommand => {
+                self.complete_command();
+                return InputResult::Success;
+            }
+            Action::HistoryPrev => {
+                self.browse_history(-1);
+                return InputResult::Success;
+            }
+            Action::HistoryNext => {
+                self.browse_history(1);
+                return InputResult::Success;
+            }
+            Action::StartDrag(index) if index < self.entry.len() => {
+                if !self.selection.contains(&self.entry[index].id) {
+                    self.selection.clear();
+                }
+                self.selected_index = index;
+                self.dragged_entry = Some((index, self.entry[index].status));
+                return InputResult::Success;
+            }
+            Action::StartDrag(_) => return InputResult::Success,
+            Action::DropDragged(status) => {
+                let Some((index, from)) = self.dragged_entry.take() else {
+                    return InputResult::Success;
+                };
+                match status {
+                    Some(status) if status != from && index < self.entry.len() => {
+                        self.selected_index = index;
+                        self.move_to(status);
+                    }
+                    _ => return InputResult::Success,
+                }
+            }
+        }
+
+        if navigation {
+            InputResult::Success
+        } else {
+            InputResult::Modified
+        }
+    }
+
+    fn cancel(&mut self) {
+        match self.input_mode {
+            InputMode::Adding
+            | InputMode::Editing
+            | InputMode::ExportPath
+            | InputMode::ImportPath
+            | InputMode::Command => {
+                self.input_mode = InputMode::Normal;
+                self.cursor_position = 0;
+            }
+            InputMode::Filter => {
+                self.input_mode = InputMode::Normal;
+                self.input.clear();
+                self.cursor_position = 0;
+                self.set_filter(String::new());
+                if let Some(index) = self.filter_saved_selection.take() {
+                    if index < self.entry.len() {
+                        self.selected_index = index;
+                    }
+                }
+            }
+            mode if mode.is_popup_input() => {
+                self.input.clear();
+                self.cursor_position = 0;
+                self.input_mode = InputMode::TotalEpisodes;
+            }
+            InputMode::TotalEpisodes => {
+                self.show_total_episodes_popup = false;
+                self.input_mode = InputMode::Normal;
+            }
+            InputMode::Activity => self.input_mode = InputMode::Normal,
+            InputMode::Normal | InputMode::Dropped if self.show_help => self.show_help = false,
+            InputMode::Normal if self.show_stats => self.show_stats = false,
+            InputMode::Normal => self.selection.clear(),
+            InputMode::Dropped => {
+                self.selection.clear();
+                self.show_dropped = false;
+                self.input_mode = InputMode::Normal;
+                self.select_first_entry_in_normal_view();
+            }
+            InputMode::ConfirmDelete | InputMode::ConfirmDeleteAllDropped => {
+                self.input_mode = if self.show_dropped {
+                    InputMode::Dropped
+                } else {
+                    InputMode::Normal
+                };
+            }
+            InputMode::ConfirmImport => {
+                self.pending_import = None;
+                self.input_mode = InputMode::Normal;
+            }
+            _ => {}
+        }
+    }
+
+    fn start_input(&mut self, mode: InputMode) {
+        let selected = self.entry.get(self.selected_index);
+        let text = match mode {
+            InputMode::Editing => match selected {
+                Some(entry) => entry.title.clone(),
+                None => return,
+            },
+            InputMode::AirDate => match selected {
+                Some(entry) => entry
+                    .next_air_date
+                    .map(|date| date.to_string())
+                    .unwrap_or_default(),
+                None => return,
+            },
+            InputMode::ExportPath | InputMode::ImportPath => self.default_export_path("json"),
+            InputMode::Filter => {
+                if self.filter.is_empty() {
+                    self.filter_saved_selection = Some(self.selected_index);
+                }
+                self.filter.clone()
+            }
+            _ if mode.is_popup_input() && selected.is_none() => return,
+            _ => String::new(),
+        };
+        self.input = text;
+        self.cursor_position = self.input.graphemes(true).count();
+        self.input_mode = mode;
+    }
+
+    fn submit_input(&mut self) -> InputResult {
+        let input: String = self.input.drain(..).collect();
+        self.cursor_position = 0;
+        match self.input_mode {
+            InputMode::Adding => {
+                self.input_mode = InputMode::Normal;
+                self.dispatch(Action::AddEntry(input))
+            }
+            InputMode::Editing => {
+                self.input_mode = InputMode::Normal;
+                self.dispatch(Action::EditTitle(input))
+            }
+            InputMode::ExportPath => {
+                self.input_mode = InputMode::Normal;
+                self.dispatch(Action::Export(input))
+            }
+            InputMode::ImportPath => self.dispatch(Action::LoadImport(input)),
+            InputMode::Filter => {
+                self.input_mode = InputMode::Normal;
+                if input.is_empty() {
+                    self.filter_saved_selection = None;
+                }
+                self.set_filter(input);
+                InputResult::Success
+            }
+            InputMode::Command => {
+                self.input_mode = InputMode::Normal;
+                self.history_index = None;
+                if !input.trim().is_empty()
+                    && self.command_history.last().map(String::as_str) != Some(input.trim())
+                {
+                    if self.command_history.len() == MAX_COMMAND_HISTORY {
+                        self.command_history.remove(0);
+                    }
+                    self.command_history.push(input.trim().to_string());
+                }
+                self.dispatch(Action::RunCommand(input))
+            }
+            mode if mode.is_popup_input() => {
+                let value = input.parse::<u32>().unwrap_or(0);
+                self.input_mode = InputMode::TotalEpisodes;
+                self.dispatch(match mode {
+                    InputMode::Runtime => Action::SetRuntime(value),
+                    InputMode::SeasonRuntime => Action::SetSeasonRuntime(value),
+                    InputMode::AirDate => Action::SetAirDate(input),
+                    InputMode::WatchSession => Action::AddWatchSession(input),
+                    _ => Action::SetMaxEpisodes(value),
+                })
+            }
+            _ => InputResult::Success,
+        }
+    }
+
+    /// Narrows the board while the filter is being typed.
+    fn filter_as_typed(&mut self) {
+        if self.input_mode == InputMode::Filter {
+            self.set_filter(self.input.clone());
+        }
+    }
+
+    /// Runs a command palette line, reporting parse errors like any other failed update.
+    fn run_command(&mut self, line: &str) -> InputResult {
+        let actions = match command::parse(line, self) {
+            Ok(actions) => actions,
+            Err(e) => {
+                self.error = Some(e);
                 self.last_error_time = Some(Instant::now());
-                return;
+                return InputResult::Success;
+            }
+        };
+        let mut result = InputResult::Success;
+        for action in actions {
+            match self.dispatch(action) {
+                InputResult::Quit => return InputResult::Quit,
+                InputResult::Modified => result = InputResult::Modified,
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Tab completion for the command palette. A single candidate is filled in, several are
+    /// narrowed to their common prefix and then cycled through on further tabs.
+    fn complete_command(&mut self) {
+        let cycling = self
+            .completion_index
+            .and_then(|i| self.completions.get(i))
+            .is_some_and(|current| *current == self.input);
+        if cycling {
+            let next = (self.completion_index.unwrap_or(0) + 1) % self.completions.len();
+            self.completion_index = Some(next);
+            self.input = self.completions[next].clone();
+        } else {
+            self.completions = command::complete(&self.input, self);
+            let prefix = common_prefix(&self.completions);
+            match self.completions.len() {
+                0 => {}
+                1 => {
+                    self.input = format!("{} ", self.completions[0]);
+                    self.completions.clear();
+                }
+                _ if prefix.len() > self.input.len() => self.input = prefix,
+                _ => {
+                    self.completion_index = Some(0);
+                    self.input = self.completions[0].clone();
+                }
+            }
+        }
+        self.cursor_position = self.input.graphemes(true).count();
+    }
+
+    /// Steps through the command history, `-1` towards older commands.
+    fn browse_history(&mut self, step: isize) {
+        if self.command_history.is_empty() {
+            return;
+        }
+        let last = self.command_history.len() - 1;
+        let index = match (self.history_index, step < 0) {
+            (None, true) => {
+                self.history_draft = self.input.clone();
+                Some(last)
+            }
+            (None, false) => return,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.history_index = index;
+        self.input = match index {
+            Some(i) => self.command_history[i].clone(),
+            None => std::mem::take(&mut self.history_draft),
+        };
+        self.cursor_position = self.input.graphemes(true).count();
+    }
+
+    /// Whether the current prompt takes `c`. The Episodes Watched prompts only take digits
+    /// (and date separators for dates).
+    fn accepts_char(&self, c: char) -> bool {
+        match self.input_mode {
+            InputMode::Adding
+            | InputMode::Editing
+            | InputMode::ExportPath
+            | InputMode::ImportPath
+            | InputMode::Command
+            | InputMode::Filter => true,
+            InputMode::AirDate | InputMode::WatchSession => {
+                c.is_ascii_digit() || matches!(c, '-' | ':' | ' ')
+            }
+            mode => mode.is_popup_input() && c.is_ascii_digit(),
+        }
+    }
+
+    /// Inserts `c` at the cursor if the prompt takes it. Titles capitalize the first letter
+    /// of each word.
+    fn insert_char(&mut self, c: char) {
+        if !self.accepts_char(c) {
+            return;
+        }
+        let starts_word = self.cursor_position == 0
+            || self
+                .input
+                .graphemes(true)
+                .nth(self.cursor_position - 1)
+                .is_some_and(is_space);
+        let text = if matches!(self.input_mode, InputMode::Adding | InputMode::Editing)
+            && starts_word
+            && c.is_alphabetic()
+        {
+            c.to_uppercase().to_string()
+        } else {
+            c.to_string()
+        };
+        self.insert_at_cursor(&text);
+    }
+
+    /// Inserts pasted text at the cursor as it is, so titles keep their capitalization. Line
+    /// breaks become spaces and characters the prompt doesn't take are left out.
+    fn paste(&mut self, text: &str) {
+        let text: String = text
+            .trim_end_matches(['\r', '\n'])
+            .replace("\r\n", " ")
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .filter(|&c| self.accepts_char(c))
+            .collect();
+        self.insert_at_cursor(&text);
+    }
+
+    fn insert_at_cursor(&mut self, text: &str) {
+        let byte_pos = self.cursor_byte_offset();
+        self.input.insert_str(byte_pos, text);
+        self.set_cursor(self.cursor_position + text.graphemes(true).count());
+    }
+
+    /// Byte offset of grapheme `position` in the input.
+    fn byte_offset(&self, position: usize) -> usize {
+        self.input
+            .graphemes(true)
+            .take(position)
+            .map(str::len)
+            .sum()
+    }
+
+    fn cursor_byte_offset(&self) -> usize {
+        self.byte_offset(self.cursor_position)
+    }
+
+    /// Start of the word before grapheme `position`, skipping the whitespace in between.
+    /// Words are separated by whitespace, as for `Ctrl-W` in a shell.
+    fn word_start_before(&self, position: usize) -> usize {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+        let mut position = position.min(graphemes.len());
+        while position > 0 && is_space(graphemes[position - 1]) {
+            position -= 1;
+        }
+        while position > 0 && !is_space(graphemes[position - 1]) {
+            position -= 1;
+        }
+        position
+    }
+
+    /// End of the word after grapheme `position`, skipping the whitespace in between.
+    fn word_end_after(&self, position: usize) -> usize {
+        let graphemes: Vec<&str> = self.input.graphemes(true).collect();
+        let mut position = position.min(graphemes.len());
+        while position < graphemes.len() && is_space(graphemes[position]) {
+            position += 1;
+        }
+        while position < graphemes.len() && !is_space(graphemes[position]) {
+            position += 1;
+        }
+        position
+    }
+
+    /// Cuts graphemes `start..end` of the input into the kill buffer, leaving the cursor
+    /// where they were. Nothing cut leaves the kill buffer alone.
+    fn kill(&mut self, start: usize, end: usize) {
+        let (from, to) = (self.byte_offset(start), self.byte_offset(end));
+        if from >= to {
+            return;
+        }
+        self.kill_buffer = self.input[from..to].to_string();
+        self.input.replace_range(from..to, "");
+        self.set_cursor(start);
+    }
+
+    fn remove_grapheme_at_cursor(&mut self) {
+        let byte_pos = self.cursor_byte_offset();
+        if let Some(grapheme) = self.input[byte_pos..].graphemes(true).next() {
+            let len = grapheme.len();
+            self.input.replace_range(byte_pos..byte_pos + len, "");
+        }
+    }
+
+    fn set_cursor(&mut self, position: usize) {
+        self.cursor_position = position.min(self.input.graphemes(true).count());
+    }
+
+    /// Moves the selection `step` entries down (up if negative) in the Dropped list.
+    fn select_next_dropped(&mut self, step: isize) {
+        let dropped = self.get_dropped_entries();
+        if dropped.is_empty() {
+            return;
+        }
+        let target = match dropped
+            .iter()
+            .position(|(idx, _)| *idx == self.selected_index)
+        {
+            Some(pos) => pos.saturating_add_signed(step).min(dropped.len() - 1),
+            None => 0,
+        };
+        self.selected_index = dropped[target].0;
+    }
+
+    /// Selects entry `row` (0-based) of the Dropped list, or its last entry.
+    fn select_dropped_row(&mut self, row: Option<usize>) {
+        let dropped = self.get_dropped_entries();
+        if let Some((idx, _)) = row.and_then(|row| dropped.get(row)).or(dropped.last()) {
+            self.selected_index = *idx;
+        }
+    }
+
+    /// The entries in column `col` of the Dropped list.
+    pub fn dropped_column(&self, col: usize) -> Vec<(usize, Entry)> {
+        let mut dropped = self.get_dropped_entries();
+        if !self.dropped_is_two_column {
+            return if col == 0 { dropped } else { Vec::new() };
+        }
+        let right = dropped.split_off(dropped.len().div_ceil(2));
+        if col == 0 {
+            dropped
+        } else {
+            right
+        }
+    }
+
+    fn scroll_dropped(&mut self, col: usize, rows: isize) {
+        let Some(height) = self
+            .dropped_column_layout
+            .get(col)
+            .map(|r| r.height as usize)
+        else {
+            return;
+        };
+        let column = self.dropped_column(col);
+        let offset = &mut self.dropped_offsets[col];
+        *offset = offset
+            .saturating_add_signed(rows)
+            .min(column.len().saturating_sub(height));
+        let offset = *offset;
+        if let Some(pos) = column
+            .iter()
+            .position(|(idx, _)| *idx == self.selected_index)
+        {
+            let pos = scroll::selection_in_view(pos, offset, column.len(), height);
+            self.selected_index = column[pos].0;
+        }
+    }
+
+    /// Jumps to the same row of the other column when the Dropped list has two.
+    fn switch_dropped_column(&mut self) {
+        if !self.dropped_is_two_column {
+            return;
+        }
+        let dropped = self.get_dropped_entries();
+        if let Some(pos) = dropped
+            .iter()
+            .position(|(idx, _)| *idx == self.selected_index)
+        {
+            let mid_point = dropped.len().div_ceil(2);
+            let target = if pos < mid_point {
+                pos + mid_point
+            } else {
+                pos - mid_point
+            };
+            if let Some((idx, _)) = dropped.get(target) {
+                self.selected_index = *idx;
             }
+        }
+    }
+
+    pub fn add_entry(&mut self, title: String) -> Result<(), AddError> {
+        match database::get_entry_by_title(&title, &self.config) {
+            Ok(Some(_)) => return Err(AddError::Duplicate(title)),
             Ok(None) => {
                 // No duplicate, proceed to add
             }
             Err(e) => {
-                self.error = Some(format!("Failed to check for existing entry: {}", e));
-                self.last_error_time = Some(Instant::now());
-                return;
+                return Err(AddError::Storage(format!(
+                    "Failed to check for existing entry: {}",
+                    e
+                )))
             }
         }
 
@@ -87,6 +935,8 @@ impl App {
             status: Status::Planning,
             watched_episodes: 0,
             max_episodes: 0,
+            updated_at: Some(database::now()),
+            ..Default::default()
         };
         match database::add_entry(&new_entry, &self.config) {
             Ok(_) => {
@@ -98,64 +948,238 @@ impl App {
 
                 self.entry.insert(insert_index, new_entry);
                 self.selected_index = insert_index;
+                Ok(())
+            }
+            Err(e) => Err(AddError::Storage(format!(
+                "Failed to add entry to database: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Finds an entry by UUID, exact title or unique title prefix, ignoring case.
+    pub fn find_entry(&self, query: &str) -> Result<usize, LookupError> {
+        if let Ok(id) = Uuid::parse_str(query) {
+            if let Some(index) = self.entry.iter().position(|e| e.id == id) {
+                return Ok(index);
+            }
+        }
+
+        let needle = query.to_lowercase();
+        if let Some(index) = self
+            .entry
+            .iter()
+            .position(|e| e.title.to_lowercase() == needle)
+        {
+            return Ok(index);
+        }
+
+        let matches: Vec<usize> = self
+            .entry
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.title.to_lowercase().starts_with(&needle))
+            .map(|(i, _)| i)
+            .collect();
+        match matches.as_slice() {
+            [index] => Ok(*index),
+            [] => Err(LookupError::NotFound(query.to_string())),
+            _ => Err(LookupError::Ambiguous(
+                query.to_string(),
+                matches
+                    .iter()
+                    .map(|&i| self.entry[i].title.clone())
+                    .collect(),
+            )),
+        }
+    }
+
+    pub fn default_export_path(&self, extension: &str) -> String {
+        Path::new(&self.config.storage_path)
+            .join(format!("senarai-export.{}", extension))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    pub fn export_entries(&mut self, path: &str) {
+        let path = PathBuf::from(shellexpand::tilde(path).to_string());
+        match formats::export_file(&self.entry, &path) {
+            Ok(_) => {
+                self.info = Some(format!(
+                    "Exported {} entries to {}",
+                    self.entry.len(),
+                    path.display()
+                ));
+                self.last_info_time = Some(Instant::now());
+            }
+            Err(e) => {
+                self.error = Some(e);
+                self.last_error_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Reads an import file and keeps it pending until `apply_import` is called.
+    pub fn load_import(&mut self, path: &str) -> bool {
+        let path = PathBuf::from(shellexpand::tilde(path).to_string());
+        match formats::import_file(&path, None, &self.config) {
+            Ok(parsed) => {
+                self.pending_import = Some(ImportPreview::new(&self.entry, parsed));
+                self.import_preview_scroll = 0;
+                true
             }
             Err(e) => {
-                self.error = Some(format!("Failed to add entry to database: {}", e));
+                self.error = Some(e);
                 self.last_error_time = Some(Instant::now());
+                false
             }
         }
     }
 
+    pub fn apply_import(&mut self, mode: ImportMode) {
+        let Some(preview) = self.pending_import.take() else {
+            return;
+        };
+
+        let plan = preview.into_plan(mode);
+        match database::replace_all_entries(&plan.entries, &self.config) {
+            Ok(_) => {
+                self.entry = plan.entries;
+                self.select_first_entry_in_normal_view();
+                self.info = Some(format!("Import complete: {}", plan.report));
+                self.last_info_time = Some(Instant::now());
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to import entries: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Moves the selected entry, or every entry picked for a bulk action, to the end of the
+    /// `status` column.
     pub fn move_to(&mut self, status: Status) {
-        if self.selected_index < self.entry.len() {
-            let mut entry_to_move = self.entry.remove(self.selected_index);
-            entry_to_move.status = status;
+        let targets = self.target_ids();
+        if targets.is_empty() {
+            return;
+        }
+        let cursor = self.entry.get(self.selected_index).map(|e| e.id);
 
-            let new_index = match status {
-                Status::Planning => self
-                    .entry
-                    .iter()
-                    .rposition(|e| e.status == Status::Planning)
-                    .map_or(0, |i| i + 1),
-                Status::Watching => self
-                    .entry
-                    .iter()
-                    .rposition(|e| e.status == Status::Planning || e.status == Status::Watching)
-                    .map_or(
-                        self.entry
-                            .iter()
-                            .position(|e| e.status == Status::Watching)
-                            .unwrap_or(0),
-                        |i| i + 1,
-                    ),
-                Status::Completed => self
-                    .entry
-                    .iter()
-                    .rposition(|e| {
-                        e.status == Status::Planning
-                            || e.status == Status::Watching
-                            || e.status == Status::Completed
-                    })
-                    .map_or(self.entry.len(), |i| i + 1),
-                Status::Dropped => self.entry.len(),
-            };
+        for id in &targets {
+            if let Some(index) = self.entry.iter().position(|e| e.id == *id) {
+                self.relocate(index, status);
+            }
+        }
+        self.selected_index = self
+            .entry
+            .iter()
+            .position(|e| Some(e.id) == cursor)
+            .unwrap_or(0);
+        if targets.len() > 1 {
+            self.selection.clear();
+        }
+
+        if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+            self.error = Some(format!("Failed to update entries in database: {}", e));
+            self.last_error_time = Some(Instant::now());
+        }
+    }
+
+    /// Takes the entry at `index` out of its column and appends it to the `status` column.
+    fn relocate(&mut self, index: usize, status: Status) {
+        let mut entry_to_move = self.entry.remove(index);
+        if entry_to_move.status != status {
+            entry_to_move.touch();
+        }
+        entry_to_move.status = status;
 
-            let insert_index = new_index.min(self.entry.len());
+        let new_index = match status {
+            Status::Planning => self
+                .entry
+                .iter()
+                .rposition(|e| e.status == Status::Planning)
+                .map_or(0, |i| i + 1),
+            Status::Watching => self
+                .entry
+                .iter()
+                .rposition(|e| e.status == Status::Planning || e.status == Status::Watching)
+                .map_or(
+                    self.entry
+                        .iter()
+                        .position(|e| e.status == Status::Watching)
+                        .unwrap_or(0),
+                    |i| i + 1,
+                ),
+            Status::Completed => self
+                .entry
+                .iter()
+                .rposition(|e| {
+                    e.status == Status::Planning
+                        || e.status == Status::Watching
+                        || e.status == Status::Completed
+                })
+                .map_or(self.entry.len(), |i| i + 1),
+            Status::Dropped => self.entry.len(),
+        };
 
-            self.entry.insert(insert_index, entry_to_move);
-            self.selected_index = insert_index;
+        let insert_index = new_index.min(self.entry.len());
+        self.entry.insert(insert_index, entry_to_move);
+    }
 
+    /// Moves the picked day, staying within the year the activity calendar shows.
+    pub fn move_activity_day(&mut self, days: i64) {
+        let today = database::now().date();
+        let first = stats::week_start(today) - Days::new((stats::ACTIVITY_WEEKS as u64 - 1) * 7);
+        let day = TimeDelta::try_days(days)
+            .and_then(|delta| self.activity_day.checked_add_signed(delta))
+            .unwrap_or(if days < 0 { first } else { today });
+        self.activity_day = day.clamp(first, today);
+    }
+
+    /// Advances the selected entry by `count` episodes, saving once.
+    pub fn next_episode(&mut self, count: u32) {
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            s.episode += count;
+            s.watched_episodes += count;
+            s.touch();
+            s.log_watched(database::now().date(), count);
+            // The air date follows the next unwatched episode, one week per episode.
+            s.next_air_date = s
+                .next_air_date
+                .map(|date| date + Days::new(7 * count as u64));
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
-                self.error = Some(format!("Failed to update entries in database: {}", e));
+                self.error = Some(format!("Failed to update entry in database: {}", e));
                 self.last_error_time = Some(Instant::now());
             }
         }
     }
 
-    pub fn next_episode(&mut self) {
+    /// Steps the selected entry back `count` episodes, into earlier seasons at episode 0,
+    /// saving once.
+    pub fn prev_episode(&mut self, count: u32) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
-            s.episode += 1;
-            s.watched_episodes += 1;
+            let mut unwatched = 0;
+            let mut stepped = false;
+            for _ in 0..count {
+                if s.episode > 0 {
+                    s.episode -= 1;
+                    s.next_air_date = s.next_air_date.map(|date| date - Days::new(7));
+                    if s.watched_episodes > 0 {
+                        s.watched_episodes -= 1;
+                        unwatched += 1;
+                    }
+                } else if s.season > 1 {
+                    s.season -= 1;
+                    s.episode = 0;
+                } else {
+                    break;
+                }
+                stepped = true;
+            }
+            if stepped {
+                s.touch();
+            }
+            s.unlog_watched(database::now().date(), unwatched);
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to update entry in database: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -163,17 +1187,16 @@ impl App {
         }
     }
 
-    pub fn prev_episode(&mut self) {
+    /// Jumps the selected entry to a season and/or episode without touching the watched count.
+    pub fn set_position(&mut self, season: Option<u32>, episode: Option<u32>) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
-            if s.episode > 0 {
-                s.episode -= 1;
-                if s.watched_episodes > 0 {
-                    s.watched_episodes -= 1;
-                }
-            } else if s.season > 1 {
-                s.season -= 1;
-                s.episode = 0;
+            if let Some(season) = season {
+                s.season = season.max(1);
             }
+            if let Some(episode) = episode {
+                s.episode = episode;
+            }
+            s.touch();
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to update entry in database: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -181,9 +1204,11 @@ impl App {
         }
     }
 
-    pub fn increment_watched_episodes(&mut self) {
+    pub fn increment_watched_episodes(&mut self, count: u32) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
-            s.watched_episodes += 1;
+            s.watched_episodes += count;
+            s.log_watched(database::now().date(), count);
+            s.touch();
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to save total episodes: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -191,10 +1216,13 @@ impl App {
         }
     }
 
-    pub fn decrement_watched_episodes(&mut self) {
+    pub fn decrement_watched_episodes(&mut self, count: u32) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
-            if s.watched_episodes > 0 {
-                s.watched_episodes -= 1;
+            let count = count.min(s.watched_episodes);
+            if count > 0 {
+                s.watched_episodes -= count;
+                s.unlog_watched(database::now().date(), count);
+                s.touch();
                 if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                     self.error = Some(format!("Failed to save total episodes: {}", e));
                     self.last_error_time = Some(Instant::now());
@@ -206,6 +1234,7 @@ impl App {
     pub fn set_max_episodes(&mut self, max_episodes: u32) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
             s.max_episodes = max_episodes;
+            s.touch();
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to save max episodes: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -213,26 +1242,119 @@ impl App {
         }
     }
 
-    pub fn selected_entry_progress(&self) -> u16 {
-        let Some(entry) = self.entry.get(self.selected_index) else {
-            return 0;
+    pub fn set_runtime(&mut self, minutes: u32) {
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            s.runtime_minutes = minutes;
+            s.touch();
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to save runtime: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Sets the runtime for the selected entry's current season; `0` clears the override.
+    pub fn set_season_runtime(&mut self, minutes: u32) {
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            if minutes == 0 {
+                s.season_runtimes.remove(&s.season);
+            } else {
+                s.season_runtimes.insert(s.season, minutes);
+            }
+            s.touch();
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to save season runtime: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Sets when the selected entry's next episode airs from `YYYY-MM-DD`; empty input clears it.
+    pub fn set_next_air_date(&mut self, input: &str) {
+        let input = input.trim();
+        let date = if input.is_empty() {
+            None
+        } else {
+            match NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+                Ok(date) => Some(date),
+                Err(_) => {
+                    self.error = Some(format!("Invalid date '{}', expected YYYY-MM-DD", input));
+                    self.last_error_time = Some(Instant::now());
+                    return;
+                }
+            }
+        };
+
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            s.next_air_date = date;
+            s.touch();
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to save air date: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Plans a watch session for the selected entry at `YYYY-MM-DD HH:MM` local time.
+    pub fn add_watch_session(&mut self, input: &str) {
+        let input = input.trim();
+        let Ok(session) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") else {
+            self.error = Some(format!(
+                "Invalid session '{}', expected YYYY-MM-DD HH:MM",
+                input
+            ));
+            self.last_error_time = Some(Instant::now());
+            return;
         };
 
-        if entry.status == Status::Completed {
-            return 100;
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            if !s.watch_sessions.contains(&session) {
+                s.watch_sessions.push(session);
+                s.watch_sessions.sort();
+            }
+            s.touch();
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to save watch session: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
         }
+    }
 
-        if entry.max_episodes == 0 {
-            return 0;
+    pub fn clear_watch_sessions(&mut self) {
+        if let Some(s) = self.entry.get_mut(self.selected_index) {
+            s.watch_sessions.clear();
+            s.touch();
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to clear watch sessions: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
         }
+    }
+
+    pub fn total_minutes_watched(&self) -> u64 {
+        self.entry.iter().map(Entry::minutes_watched).sum()
+    }
 
-        ((entry.watched_episodes.saturating_mul(100)) / entry.max_episodes).min(100) as u16
+    /// Sum of the known remaining time of all entries with the given status.
+    pub fn total_minutes_remaining(&self, status: Status) -> u64 {
+        self.entry
+            .iter()
+            .filter(|e| e.status == status)
+            .filter_map(Entry::minutes_remaining)
+            .sum()
+    }
+
+    pub fn selected_entry_progress(&self) -> u16 {
+        self.entry
+            .get(self.selected_index)
+            .map_or(0, |entry| entry.progress())
     }
 
-    pub fn next_season(&mut self) {
+    pub fn next_season(&mut self, count: u32) {
         if let Some(s) = self.entry.get_mut(self.selected_index) {
-            s.season += 1;
+            s.season += count;
             s.episode = 0;
+            s.touch();
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to update entry in database: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -247,8 +1369,8 @@ impl App {
         let current_status = self.entry[self.selected_index].status;
         let mut next_status = current_status.next();
         for _ in 0..2 {
-            if let Some(idx) = self.entry.iter().position(|s| s.status == next_status) {
-                self.selected_index = idx;
+            if let Some((idx, _)) = self.get_entries_by_status(next_status).first() {
+                self.selected_index = *idx;
                 return;
             }
             next_status = next_status.next();
@@ -262,22 +1384,348 @@ impl App {
         let current_status = self.entry[self.selected_index].status;
         let mut prev_status = current_status.prev();
         for _ in 0..2 {
-            if let Some(idx) = self.entry.iter().position(|s| s.status == prev_status) {
-                self.selected_index = idx;
+            if let Some((idx, _)) = self.get_entries_by_status(prev_status).first() {
+                self.selected_index = *idx;
                 return;
             }
             prev_status = prev_status.prev();
         }
     }
 
-    pub fn get_entries_by_status(&self, status: Status) -> Vec<(usize, &Entry)> {
+    /// Indexes of the picked entries that are visible in the current view: the board, or the
+    /// Dropped list while it is open.
+    pub fn selected_entries(&self) -> Vec<usize> {
         self.entry
             .iter()
             .enumerate()
-            .filter(|(_, entry)| entry.status == status)
+            .filter(|(_, e)| self.selection.contains(&e.id))
+            .filter(|(_, e)| {
+                if self.show_dropped {
+                    e.status == Status::Dropped
+                } else {
+                    e.status != Status::Dropped && self.matches_filter(e)
+                }
+            })
+            .map(|(i, _)| i)
             .collect()
     }
 
+    /// Ids of the entries a bulk-capable action works on: the visible picked entries, or
+    /// else the selected entry.
+    fn target_ids(&self) -> Vec<Uuid> {
+        let picked = self.selected_entries();
+        if picked.is_empty() {
+            self.entry
+                .get(self.selected_index)
+                .map(|e| vec![e.id])
+                .unwrap_or_default()
+        } else {
+            picked.into_iter().map(|i| self.entry[i].id).collect()
+        }
+    }
+
+    /// The entries listed together with the selected one: its column, or the Dropped list.
+    fn entries_in_view(&self) -> Vec<usize> {
+        if self.show_dropped {
+            return self
+                .get_dropped_entries()
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect();
+        }
+        match self.entry.get(self.selected_index) {
+            Some(entry) => self
+                .get_entries_by_status(entry.status)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Picks or unpicks the selected entry and moves on to the next row.
+    pub fn toggle_selected(&mut self) {
+        let Some(id) = self.entry.get(self.selected_index).map(|e| e.id) else {
+            return;
+        };
+        if !self.selection.remove(&id) {
+            self.selection.insert(id);
+        }
+        self.selection_anchor = Some(id);
+        if self.show_dropped {
+            self.select_next_dropped(1);
+        } else {
+            self.move_selection_in_column(1);
+        }
+    }
+
+    /// Picks every entry between the last toggled one and `index` in the same list, and
+    /// selects `index`.
+    pub fn select_range(&mut self, index: usize) {
+        let Some(id) = self.entry.get(index).map(|e| e.id) else {
+            return;
+        };
+        self.selected_index = index;
+        let view = self.entries_in_view();
+        let anchor = self
+            .selection_anchor
+            .and_then(|anchor| view.iter().position(|i| self.entry[*i].id == anchor));
+        match (anchor, view.iter().position(|i| *i == index)) {
+            (Some(from), Some(to)) => {
+                for i in &view[from.min(to)..=from.max(to)] {
+                    self.selection.insert(self.entry[*i].id);
+                }
+            }
+            _ => {
+                self.selection.insert(id);
+                self.selection_anchor = Some(id);
+            }
+        }
+    }
+
+    /// Adds (or with `add` false, removes) a tag on the selected or picked entries.
+    pub fn tag_entries(&mut self, tag: &str, add: bool) {
+        let targets = self.target_ids();
+        let mut changed = 0;
+        for entry in self.entry.iter_mut().filter(|e| targets.contains(&e.id)) {
+            let done = if add {
+                entry.add_tag(tag)
+            } else {
+                entry.remove_tag(tag)
+            };
+            if done {
+                entry.touch();
+            }
+            changed += done as usize;
+        }
+        if changed == 0 {
+            return;
+        }
+        if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+            self.error = Some(format!("Failed to update tags in database: {}", e));
+            self.last_error_time = Some(Instant::now());
+            return;
+        }
+        self.info = Some(format!(
+            "{} '{}' {} {} {}",
+            if add { "Added" } else { "Removed" },
+            tag.trim().to_lowercase(),
+            if add { "to" } else { "from" },
+            changed,
+            if changed == 1 { "entry" } else { "entries" }
+        ));
+        self.last_info_time = Some(Instant::now());
+    }
+
+    /// Moves the picked entries of the selected column one row up (or down) together.
+    /// Returns whether they moved, or `None` if nothing in the column is picked, so a single
+    /// entry should move.
+    fn move_block(&mut self, up: bool) -> Option<bool> {
+        let cursor = self.entry.get(self.selected_index)?.id;
+        let slots = self.entries_in_view();
+        let mut picked: Vec<bool> = slots
+            .iter()
+            .map(|i| self.selection.contains(&self.entry[*i].id))
+            .collect();
+        if !picked.contains(&true) {
+            return None;
+        }
+        let blocked = if up { picked.first() } else { picked.last() };
+        if blocked == Some(&true) {
+            return Some(false);
+        }
+
+        let order: Vec<usize> = if up {
+            (1..slots.len()).collect()
+        } else {
+            (0..slots.len() - 1).rev().collect()
+        };
+        for pos in order {
+            let other = if up { pos - 1 } else { pos + 1 };
+            if picked[pos] && !picked[other] {
+                self.entry.swap(slots[pos], slots[other]);
+                picked.swap(pos, other);
+            }
+        }
+        self.selected_index = self
+            .entry
+            .iter()
+            .position(|e| e.id == cursor)
+            .unwrap_or(self.selected_index);
+        Some(true)
+    }
+
+    /// The entries shown in a column: those with `status` that match the filter, in the
+    /// column's sort order.
+    pub fn get_entries_by_status(&self, status: Status) -> Vec<(usize, &Entry)> {
+        let mut entries: Vec<(usize, &Entry)> = self
+            .entry
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.status == status && self.matches_filter(entry))
+            .collect();
+        self.sort_mode(status).sort(&mut entries);
+        entries
+    }
+
+    pub fn sort_mode(&self, status: Status) -> SortMode {
+        self.sort_modes.get(&status).copied().unwrap_or_default()
+    }
+
+    /// Sorts the selected entry's column by `mode`, or by the next mode for `None`.
+    pub fn set_sort_mode(&mut self, mode: Option<SortMode>) {
+        let Some(status) = self
+            .entry
+            .get(self.selected_index)
+            .map(|entry| entry.status)
+            .filter(|status| *status != Status::Dropped)
+        else {
+            return;
+        };
+        let mode = mode.unwrap_or_else(|| self.sort_mode(status).next());
+        self.sort_modes.insert(status, mode);
+        if let Err(e) = database::set_sort_mode(status, mode, &self.config) {
+            self.error = Some(format!("Failed to save sort mode: {}", e));
+            self.last_error_time = Some(Instant::now());
+        }
+    }
+
+    /// Reordering by hand only makes sense while the column shows the manual order.
+    fn can_reorder(&mut self) -> bool {
+        let Some(status) = self.entry.get(self.selected_index).map(|e| e.status) else {
+            return false;
+        };
+        let mode = self.sort_mode(status);
+        if mode != SortMode::Manual {
+            self.error = Some(format!(
+                "{} is sorted by {}; switch to manual sort to reorder",
+                status, mode
+            ));
+            self.last_error_time = Some(Instant::now());
+            return false;
+        }
+        true
+    }
+
+    pub fn matches_filter(&self, entry: &Entry) -> bool {
+        if self.filter.trim().is_empty() {
+            return true;
+        }
+        let (title_query, tags) = fuzzy::split_tag_terms(&self.filter);
+        fuzzy::fuzzy_match(&title_query, &entry.title).is_some()
+            && tags
+                .iter()
+                .all(|tag| entry.tags.iter().any(|t| t.starts_with(tag.as_str())))
+    }
+
+    /// Whether the selected entry is on the board, i.e. not dropped or filtered out.
+    pub fn selection_visible(&self) -> bool {
+        self.entry
+            .get(self.selected_index)
+            .is_some_and(|entry| entry.status != Status::Dropped && self.matches_filter(entry))
+    }
+
+    /// Filters the board, moving the selection to the first match if it was filtered out.
+    pub fn set_filter(&mut self, query: String) {
+        self.filter = query;
+        if !self.selection_visible() {
+            self.select_first_entry_in_normal_view();
+        }
+    }
+
+    /// Moves the selection `rows` entries down (up if negative) in its column, stopping at
+    /// the first and last entry.
+    pub fn move_selection_in_column(&mut self, rows: isize) {
+        let Some(entry) = self.entry.get(self.selected_index) else {
+            return;
+        };
+        let column = self.get_entries_by_status(entry.status);
+        if let Some(pos) = column
+            .iter()
+            .position(|(idx, _)| *idx == self.selected_index)
+        {
+            let target = pos.saturating_add_signed(rows).min(column.len() - 1);
+            self.selected_index = column[target].0;
+        }
+    }
+
+    /// Selects entry `row` (0-based) of the selected column, or its last entry if `row` is
+    /// past the end or `None`.
+    pub fn select_row_in_column(&mut self, row: Option<usize>) {
+        let Some(entry) = self.entry.get(self.selected_index) else {
+            return;
+        };
+        let column = self.get_entries_by_status(entry.status);
+        if let Some((idx, _)) = row.and_then(|row| column.get(row)).or(column.last()) {
+            self.selected_index = *idx;
+        }
+    }
+
+    /// Rows of entries visible in board column `status` when it was last drawn.
+    fn column_rows(&self, status: Status) -> usize {
+        Status::BOARD
+            .iter()
+            .position(|s| *s == status)
+            .and_then(|col| self.column_layout.get(col))
+            .map_or(0, |r| r.height.saturating_sub(2) as usize)
+    }
+
+    pub fn scroll_column(&mut self, status: Status, rows: isize) {
+        let height = self.column_rows(status);
+        if height == 0 {
+            return;
+        }
+        let column = self.get_entries_by_status(status);
+        let offset = self.column_offsets.get(&status).copied().unwrap_or(0);
+        let offset = offset
+            .saturating_add_signed(rows)
+            .min(column.len().saturating_sub(height));
+        let selected = column
+            .iter()
+            .position(|(idx, _)| *idx == self.selected_index)
+            .map(|pos| column[scroll::selection_in_view(pos, offset, column.len(), height)].0);
+        self.column_offsets.insert(status, offset);
+        if let Some(selected) = selected {
+            self.selected_index = selected;
+        }
+    }
+
+    /// Rows of entries in the tallest list on screen: the Dropped list while it is open,
+    /// otherwise the board columns inside their borders.
+    fn visible_rows(&self) -> usize {
+        let rows = if self.show_dropped {
+            self.dropped_column_layout.iter().map(|r| r.height).max()
+        } else {
+            self.column_layout
+                .iter()
+                .map(|r| r.height.saturating_sub(2))
+                .max()
+        };
+        rows.unwrap_or(0) as usize
+    }
+
+    /// Rows in a page, less one row kept for context.
+    pub fn page(&self) -> usize {
+        self.visible_rows().saturating_sub(1).max(1)
+    }
+
+    /// Half the number of rows visible in a column, for half-page jumps.
+    pub fn half_page(&self) -> usize {
+        (self.visible_rows() / 2).max(1)
+    }
+
+    /// The count and keys typed so far, like `5` or `g`, for the footer.
+    pub fn pending_keys_text(&self) -> Option<String> {
+        if self.count.is_none() && self.pending_keys.is_empty() {
+            return None;
+        }
+        let mut text = self.count.map(|c| c.to_string()).unwrap_or_default();
+        for chord in &self.pending_keys {
+            text.push_str(&chord.to_string());
+        }
+        Some(text)
+    }
+
     pub fn get_dropped_entries(&self) -> Vec<(usize, Entry)> {
         self.entry
             .iter()
@@ -353,28 +1801,43 @@ impl App {
     }
 
     pub fn drop_entry(&mut self) {
-        if let Some(entry) = self.entry.get_mut(self.selected_index) {
+        let targets = self.target_ids();
+        if targets.is_empty() {
+            return;
+        }
+        for entry in self.entry.iter_mut().filter(|e| targets.contains(&e.id)) {
             entry.status = Status::Dropped;
-            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
-                self.error = Some(format!("Failed to drop entry in database: {}", e));
-                self.last_error_time = Some(Instant::now());
-            }
-            self.select_next_or_prev();
+            entry.touch();
         }
+        self.selection.clear();
+        if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+            self.error = Some(format!("Failed to drop entry in database: {}", e));
+            self.last_error_time = Some(Instant::now());
+        }
+        self.select_next_or_prev();
     }
 
+    /// Deletes the selected entry, or every entry picked for a bulk action, for good.
     pub fn force_remove_entry(&mut self) {
-        if !self.entry.is_empty() {
-            let entry_id = self.entry[self.selected_index].id;
-            match database::delete_entry(&entry_id, &self.config) {
-                Ok(_) => {
-                    self.entry.remove(self.selected_index);
-                    self.select_next_or_prev();
-                }
-                Err(e) => {
-                    self.error = Some(format!("Failed to delete entry from database: {}", e));
-                    self.last_error_time = Some(Instant::now());
-                }
+        let targets = self.target_ids();
+        if targets.is_empty() {
+            return;
+        }
+        match database::delete_entries(&targets, &self.config) {
+            Ok(_) => {
+                let first_removed = self
+                    .entry
+                    .iter()
+                    .position(|e| targets.contains(&e.id))
+                    .unwrap_or(self.selected_index);
+                self.entry.retain(|e| !targets.contains(&e.id));
+                self.selection.clear();
+                self.selected_index = first_removed;
+                self.select_next_or_prev();
+            }
+            Err(e) => {
+                self.error = Some(format!("Failed to delete entry from database: {}", e));
+                self.last_error_time = Some(Instant::now());
             }
         }
     }
@@ -415,8 +1878,19 @@ impl App {
     }
 
     pub fn edit_entry_title(&mut self, new_title: String) {
+        let taken = self
+            .entry
+            .iter()
+            .enumerate()
+            .any(|(i, e)| i != self.selected_index && e.title == new_title);
+        if taken {
+            self.error = Some(format!("Entry with title '{}' already exists.", new_title));
+            self.last_error_time = Some(Instant::now());
+            return;
+        }
         if let Some(s) = self.entry.get_mut(self.selected_index) {
             s.title = new_title;
+            s.touch();
             if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
                 self.error = Some(format!("Failed to update entry title in database: {}", e));
                 self.last_error_time = Some(Instant::now());
@@ -424,60 +1898,66 @@ impl App {
         }
     }
 
-    pub fn move_entry_up_in_column(&mut self) {
-        if self.entry.is_empty() {
-            return;
-        }
-
-        let current_entry_status = self.entry[self.selected_index].status;
-        let entries_in_current_status = self.get_entries_by_status(current_entry_status);
-
-        let current_entry_pos_in_status = entries_in_current_status
-            .iter()
-            .position(|(idx, _)| *idx == self.selected_index);
-
-        if let Some(pos) = current_entry_pos_in_status {
-            if pos > 0 {
-                let (global_idx_current, _) = entries_in_current_status[pos];
-                let (global_idx_prev, _) = entries_in_current_status[pos - 1];
+    pub fn move_entry_up_in_column(&mut self, count: u32) {
+        self.move_in_column(true, count);
+    }
 
-                self.entry.swap(global_idx_current, global_idx_prev);
-                self.selected_index = global_idx_prev;
+    pub fn move_entry_down_in_column(&mut self, count: u32) {
+        self.move_in_column(false, count);
+    }
 
-                if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
-                    self.error = Some(format!("Failed to update entries in database: {}", e));
-                    self.last_error_time = Some(Instant::now());
-                }
+    /// Moves the selected entry, or the picked ones as a block, `count` rows up (or down)
+    /// and saves the new order once.
+    fn move_in_column(&mut self, up: bool, count: u32) {
+        if self.entry.is_empty() || !self.can_reorder() {
+            return;
+        }
+        let mut moved = false;
+        for _ in 0..count {
+            let step = match self.move_block(up) {
+                Some(step) => step,
+                None => self.move_selected(up),
+            };
+            if !step {
+                break;
             }
+            moved = true;
         }
-    }
 
-    pub fn move_entry_down_in_column(&mut self) {
-        if self.entry.is_empty() {
-            return;
+        if moved {
+            if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
+                self.error = Some(format!("Failed to update entries in database: {}", e));
+                self.last_error_time = Some(Instant::now());
+            }
         }
+    }
 
+    /// Swaps the selected entry with its neighbour above (or below) in its column.
+    /// Returns false at the edge of the column.
+    fn move_selected(&mut self, up: bool) -> bool {
         let current_entry_status = self.entry[self.selected_index].status;
         let entries_in_current_status = self.get_entries_by_status(current_entry_status);
 
-        let current_entry_pos_in_status = entries_in_current_status
+        let Some(pos) = entries_in_current_status
             .iter()
-            .position(|(idx, _)| *idx == self.selected_index);
-
-        if let Some(pos) = current_entry_pos_in_status {
-            if pos + 1 < entries_in_current_status.len() {
-                let (global_idx_current, _) = entries_in_current_status[pos];
-                let (global_idx_next, _) = entries_in_current_status[pos + 1];
-
-                self.entry.swap(global_idx_current, global_idx_next);
-                self.selected_index = global_idx_next;
+            .position(|(idx, _)| *idx == self.selected_index)
+        else {
+            return false;
+        };
+        let other = if up {
+            pos.checked_sub(1)
+        } else {
+            Some(pos + 1).filter(|next| *next < entries_in_current_status.len())
+        };
+        let Some(other) = other else {
+            return false;
+        };
 
-                if let Err(e) = database::update_all_entries(&self.entry, &self.config) {
-                    self.error = Some(format!("Failed to update entries in database: {}", e));
-                    self.last_error_time = Some(Instant::now());
-                }
-            }
-        }
+        let (global_idx_current, _) = entries_in_current_status[pos];
+        let (global_idx_other, _) = entries_in_current_status[other];
+        self.entry.swap(global_idx_current, global_idx_other);
+        self.selected_index = global_idx_other;
+        true
     }
 
     pub fn force_remove_all_dropped_entries(&mut self) {
@@ -492,27 +1972,16 @@ impl App {
             return;
         }
 
-        let mut has_error = false;
-        for id in &dropped_entry_ids {
-            if let Err(e) = database::delete_entry(id, &self.config) {
-                self.error = Some(format!("Failed to delete entry from database: {}", e));
-                self.last_error_time = Some(Instant::now());
-                has_error = true;
-                break;
-            }
-        }
-
-        if !has_error {
+        if let Err(e) = database::delete_entries(&dropped_entry_ids, &self.config) {
+            self.error = Some(format!("Failed to delete entry from database: {}", e));
+            self.last_error_time = Some(Instant::now());
+        } else {
             let original_len = self.entry.len();
             self.entry.retain(|entry| entry.status != Status::Dropped);
             let removed_count = original_len - self.entry.len();
 
             if removed_count > 0 {
-                if !self.entry.is_empty() {
-                    self.selected_index = 0;
-                } else {
-                    self.selected_index = 0;
-                }
+                self.selected_index = 0;
             }
         }
     }
@@ -533,3 +2002,59 @@ impl App {
         }
     }
 }
+
+/// Longest prefix shared by all `candidates`, cut at a character boundary.
+fn common_prefix(candidates: &[String]) -> String {
+    let Some(first) = candidates.first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for candidate in &candidates[1..] {
+        len = first
+            .char_indices()
+            .zip(candidate.chars())
+            .take_while(|((_, a), b)| a.to_lowercase().eq(b.to_lowercase()))
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(len);
+    }
+    first[..len].to_string()
+}
+
+/// Whether a grapheme is whitespace, which separates words for the word motions.
+fn is_space(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Actions that only move the selection or change what is shown.
+fn action_is_navigation(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::SelectNext
+            | Action::SelectPrev
+            | Action::SelectNextColumn
+            | Action::SelectPrevColumn
+            | Action::SelectRow(_)
+            | Action::MoveSelection(_)
+            | Action::ScrollColumn(..)
+            | Action::ScrollDropped(..)
+            | Action::Select(_)
+            | Action::SelectColumn(_)
+            | Action::ToggleHelp
+            | Action::ToggleFullTitle
+            | Action::ToggleDetails
+            | Action::ToggleStats
+            | Action::OpenEpisodes
+            | Action::OpenDropped
+            | Action::OpenActivity
+            | Action::MoveActivityDay(_)
+            | Action::Cancel
+            | Action::SetFilter(_)
+            | Action::SetSort(_)
+            | Action::ToggleSelected
+            | Action::SelectRange(_)
+            | Action::RequestRemove
+            | Action::RequestRemoveAllDropped
+    )
+}
@@ -0,0 +1,544 @@
+use crate::app::{AddError, App, LookupError};
+use crate::config::{self, Config, ConfigArgs};
+use crate::formats::report::{self, ReportFormat};
+use crate::import::{self, ImportMode};
+use crate::stats::Stats;
+use crate::{database, format_minutes, formats, Entry, Status};
+use chrono::Local;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Column order used when listing, matching the TUI.
+const BOARD_ORDER: [Status; 4] = [
+    Status::Planning,
+    Status::Watching,
+    Status::Completed,
+    Status::Dropped,
+];
+
+#[derive(Parser)]
+#[command(name = "senarai", version, about = "A terminal watchlist manager")]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Write a commented default config file
+    Init {
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Add a new entry to Planning
+    Add { title: String },
+    /// List entries in board order
+    List {
+        /// Only show entries with this status
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+        /// Print the entries as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a single entry
+    Show {
+        /// Title, unique title prefix or id
+        entry: String,
+        /// Print the entry as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Summarize the watchlist
+    Stats {
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Watch the next episode of an entry
+    Inc {
+        /// Title, unique title prefix or id
+        entry: String,
+    },
+    /// Jump an entry to a season and/or episode
+    Set {
+        /// Title, unique title prefix or id
+        entry: String,
+        #[arg(long)]
+        season: Option<u32>,
+        #[arg(long)]
+        episode: Option<u32>,
+    },
+    /// Change the status of an entry
+    Move {
+        /// Title, unique title prefix or id
+        entry: String,
+        #[arg(value_enum)]
+        status: Status,
+    },
+    /// Drop an entry, or delete it for good with --purge
+    Rm {
+        /// Title, unique title prefix or id
+        entry: String,
+        #[arg(long)]
+        purge: bool,
+    },
+    /// Export the whole watchlist
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Layout file for markdown and html reports
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+    /// Import a watchlist export
+    Import {
+        file: PathBuf,
+        /// File format (json, trakt, mal, letterboxd, tvtime, csv), detected by default
+        #[arg(long)]
+        format: Option<String>,
+        #[arg(long, value_enum, default_value_t = ImportMode::Merge)]
+        mode: ImportMode,
+        /// Only report what would change
+        #[arg(long)]
+        dry_run: bool,
+        /// CSV column mapping as field=Header (fields: title, status, season, episode, watched, max)
+        #[arg(long = "map", value_name = "FIELD=HEADER")]
+        columns: Vec<String>,
+        /// Status for entries marked "on hold" or "paused"
+        #[arg(long, value_enum)]
+        on_hold: Option<Status>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the config file and the storage path
+    Check,
+    /// Print the effective configuration and where each value comes from
+    Show,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    /// MyAnimeList XML
+    Mal,
+    /// Markdown report grouped by status
+    Markdown,
+    /// Self-contained HTML report grouped by status
+    Html,
+    /// iCalendar file with upcoming episodes and watch sessions
+    Ics,
+}
+
+/// A failed command, classified so scripts can tell failures apart by exit code.
+#[derive(Debug)]
+pub enum CliError {
+    NotFound(String),
+    Ambiguous(String),
+    Storage(String),
+    Other(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Other(_) => 1,
+            CliError::NotFound(_) => 3,
+            CliError::Ambiguous(_) => 4,
+            CliError::Storage(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::NotFound(e)
+            | CliError::Ambiguous(e)
+            | CliError::Storage(e)
+            | CliError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(e: String) -> Self {
+        CliError::Other(e)
+    }
+}
+
+impl From<LookupError> for CliError {
+    fn from(e: LookupError) -> Self {
+        match e {
+            LookupError::NotFound(_) => CliError::NotFound(e.to_string()),
+            LookupError::Ambiguous(..) => CliError::Ambiguous(e.to_string()),
+        }
+    }
+}
+
+impl From<AddError> for CliError {
+    fn from(e: AddError) -> Self {
+        match e {
+            AddError::Duplicate(_) => CliError::Other(e.to_string()),
+            AddError::Storage(_) => CliError::Storage(e.to_string()),
+        }
+    }
+}
+
+/// An entry with the derived fields shown by `--json`.
+#[derive(Serialize)]
+struct EntryView<'a> {
+    #[serde(flatten)]
+    entry: &'a Entry,
+    progress: u16,
+    /// `None` while the total number of episodes is unknown.
+    remaining_episodes: Option<u32>,
+    minutes_watched: u64,
+    minutes_remaining: Option<u64>,
+}
+
+impl<'a> EntryView<'a> {
+    fn new(entry: &'a Entry) -> Self {
+        Self {
+            entry,
+            progress: entry.progress(),
+            remaining_episodes: entry.episodes_remaining(),
+            minutes_watched: entry.minutes_watched(),
+            minutes_remaining: entry.minutes_remaining(),
+        }
+    }
+}
+
+pub fn run(command: Command, args: &ConfigArgs) -> Result<(), CliError> {
+    match command {
+        Command::Init { force } => init(args, force),
+        Command::Config {
+            action: ConfigCommand::Check,
+        } => check(args),
+        Command::Config {
+            action: ConfigCommand::Show,
+        } => show(args),
+        command => {
+            let config = config::load_config(args).map_err(CliError::Other)?;
+            run_with_config(command, &config)
+        }
+    }
+}
+
+fn init(args: &ConfigArgs, force: bool) -> Result<(), CliError> {
+    let path = config::init_config_path(args).ok_or_else(|| {
+        CliError::Other("Can't find a config directory, pass --config <file>".to_string())
+    })?;
+    if path.exists() && !force {
+        return Err(CliError::Other(format!(
+            "{} already exists, pass --force to overwrite it",
+            path.display()
+        )));
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, config::DEFAULT_CONFIG)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn check(args: &ConfigArgs) -> Result<(), CliError> {
+    let (path, diagnostics) = config::check_config(args)?;
+    let name = match &path {
+        Some(path) => path.display().to_string(),
+        None => "defaults".to_string(),
+    };
+
+    for diagnostic in &diagnostics {
+        match diagnostic.line {
+            Some(line) => println!("{}:{}: {}", name, line, diagnostic.message),
+            None => println!("{}: {}", name, diagnostic.message),
+        }
+    }
+
+    match diagnostics.len() {
+        0 => {
+            println!("{}: ok", name);
+            Ok(())
+        }
+        1 => Err(CliError::Other("1 problem found".to_string())),
+        n => Err(CliError::Other(format!("{} problems found", n))),
+    }
+}
+
+fn show(args: &ConfigArgs) -> Result<(), CliError> {
+    match config::locate_config_file(args)? {
+        Some((path, source)) => println!("# config file: {} ({})", path.display(), source),
+        None => println!("# config file: none, using defaults"),
+    }
+
+    let settings = config::describe_config(args)?;
+    let width = settings
+        .iter()
+        .map(|(key, value, _)| key.len() + value.len())
+        .max()
+        .unwrap_or(0);
+    for (key, value, source) in settings {
+        let setting = format!("{}: {}", key, value);
+        println!("{:<width$}  # {}", setting, source, width = width + 2);
+    }
+    Ok(())
+}
+
+fn run_with_config(command: Command, config: &Config) -> Result<(), CliError> {
+    database::init_db(config).map_err(|e| CliError::Storage(e.to_string()))?;
+    let entries = database::load_entry(config).map_err(|e| CliError::Storage(e.to_string()))?;
+
+    let mut app = App::new(entries, config.clone());
+
+    match command {
+        Command::Add { title } => {
+            let title = title.trim().to_string();
+            if title.is_empty() {
+                return Err(CliError::Other("Title must not be empty".to_string()));
+            }
+            app.add_entry(title)?;
+            print_entry(&app.entry[app.selected_index]);
+        }
+        Command::List { status, json } => {
+            let mut listed: Vec<&Entry> = app
+                .entry
+                .iter()
+                .filter(|e| status.is_none_or(|status| e.status == status))
+                .collect();
+            listed.sort_by_key(|e| BOARD_ORDER.iter().position(|s| *s == e.status));
+            if json {
+                let views: Vec<EntryView> = listed.into_iter().map(EntryView::new).collect();
+                print_json(&views)?;
+            } else {
+                for entry in listed {
+                    print_entry(entry);
+                }
+            }
+        }
+        Command::Show { entry, json } => {
+            let entry = &app.entry[app.find_entry(&entry)?];
+            if json {
+                print_json(&EntryView::new(entry))?;
+            } else {
+                print_details(entry);
+            }
+        }
+        Command::Stats { json } => {
+            let stats = Stats::new(&app.entry, database::now().date());
+            if json {
+                print_json(&stats)?;
+            } else {
+                print_stats(&stats);
+            }
+        }
+        Command::Inc { entry } => {
+            app.selected_index = app.find_entry(&entry)?;
+            app.next_episode(1);
+            finish(&mut app)?;
+            print_entry(&app.entry[app.selected_index]);
+        }
+        Command::Set {
+            entry,
+            season,
+            episode,
+        } => {
+            if season.is_none() && episode.is_none() {
+                return Err(CliError::Other(
+                    "Nothing to set, pass --season and/or --episode".to_string(),
+                ));
+            }
+            app.selected_index = app.find_entry(&entry)?;
+            app.set_position(season, episode);
+            finish(&mut app)?;
+            print_entry(&app.entry[app.selected_index]);
+        }
+        Command::Move { entry, status } => {
+            app.selected_index = app.find_entry(&entry)?;
+            app.move_to(status);
+            finish(&mut app)?;
+            print_entry(&app.entry[app.selected_index]);
+        }
+        Command::Rm { entry, purge } => {
+            app.selected_index = app.find_entry(&entry)?;
+            let title = app.entry[app.selected_index].title.clone();
+            if purge {
+                app.force_remove_entry();
+                finish(&mut app)?;
+                println!("Deleted {}", title);
+            } else {
+                app.drop_entry();
+                finish(&mut app)?;
+                println!("Dropped {}", title);
+            }
+        }
+        Command::Init { .. } | Command::Config { .. } => unreachable!("handled by run"),
+        Command::Export {
+            format,
+            output,
+            template,
+        } => {
+            let template = report::read_template(template.as_deref())?;
+            if template.is_some() && !matches!(format, ExportFormat::Markdown | ExportFormat::Html)
+            {
+                return Err(CliError::Other(
+                    "--template only applies to markdown and html exports".to_string(),
+                ));
+            }
+
+            let data = match format {
+                ExportFormat::Json => formats::json::to_string(&app.entry)?,
+                ExportFormat::Csv => formats::csv::to_string(&app.entry)?,
+                ExportFormat::Mal => formats::mal::to_string(&app.entry),
+                ExportFormat::Markdown => {
+                    report::render(&app.entry, ReportFormat::Markdown, template.as_deref())
+                }
+                ExportFormat::Html => {
+                    report::render(&app.entry, ReportFormat::Html, template.as_deref())
+                }
+                ExportFormat::Ics => {
+                    formats::ics::to_string(&app.entry, Local::now().naive_local())
+                }
+            };
+            match output {
+                Some(path) => std::fs::write(&path, data)
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?,
+                None if data.ends_with('\n') => print!("{}", data),
+                None => println!("{}", data),
+            }
+        }
+        Command::Import {
+            file,
+            format,
+            mode,
+            dry_run,
+            columns,
+            on_hold,
+        } => {
+            let mut config = config.clone();
+            for assignment in &columns {
+                config.csv.set_column(assignment)?;
+            }
+            if let Some(status) = on_hold {
+                config.import.on_hold_status = status;
+            }
+
+            let incoming = formats::import_file(&file, format.as_deref(), &config)?;
+            let plan = import::plan(&app.entry, incoming, mode);
+
+            for line in plan.report.details() {
+                println!("{}", line);
+            }
+            println!("{}", plan.report);
+
+            if dry_run {
+                println!("Dry run, nothing was written.");
+            } else if plan.report.has_changes() {
+                database::replace_all_entries(&plan.entries, &config)
+                    .map_err(|e| CliError::Storage(format!("Failed to import entries: {}", e)))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns a database error the `App` recorded for the TUI into a command failure.
+fn finish(app: &mut App) -> Result<(), CliError> {
+    match app.error.take() {
+        Some(e) => Err(CliError::Storage(e)),
+        None => Ok(()),
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
+    let data = serde_json::to_string_pretty(value)
+        .map_err(|e| CliError::Other(format!("Failed to serialize output: {}", e)))?;
+    println!("{}", data);
+    Ok(())
+}
+
+fn print_details(entry: &Entry) {
+    let view = EntryView::new(entry);
+    println!("{}", entry.title);
+    println!("  id:        {}", entry.id);
+    println!("  status:    {}", entry.status);
+    println!("  position:  S{} E{}", entry.season, entry.episode);
+    match view.remaining_episodes {
+        Some(remaining) => println!(
+            "  episodes:  {}/{} watched, {} remaining ({}%)",
+            entry.watched_episodes, entry.max_episodes, remaining, view.progress
+        ),
+        None => println!("  episodes:  {} watched", entry.watched_episodes),
+    }
+    if entry.runtime_minutes > 0 || !entry.season_runtimes.is_empty() {
+        println!("  watched:   {}", format_minutes(view.minutes_watched));
+        if let Some(minutes) = view.minutes_remaining {
+            println!("  remaining: {}", format_minutes(minutes));
+        }
+    }
+    if !entry.tags.is_empty() {
+        println!("  tags:      {}", entry.tags.join(", "));
+    }
+}
+
+fn print_stats(stats: &Stats) {
+    let percent = |value: Option<u16>| value.map_or("n/a".to_string(), |v| format!("{}%", v));
+    println!("entries:           {}", stats.entries);
+    for count in &stats.per_status {
+        println!("  {:<16} {}", count.status.to_string(), count.entries);
+    }
+    println!("episodes watched:  {}", stats.episodes_watched);
+    println!("completion rate:   {}", percent(stats.completion_rate));
+    println!("watching progress: {}", percent(stats.watching_progress));
+    println!("current streak:    {} days", stats.current_streak);
+    println!("longest streak:    {} days", stats.longest_streak);
+    if !stats.longest_running.is_empty() {
+        println!("longest running:");
+        for show in &stats.longest_running {
+            println!("  {:>5}  {}", show.episodes, show.title);
+        }
+    }
+    if !stats.episodes_per_week.is_empty() {
+        println!("episodes per week:");
+        for week in &stats.episodes_per_week {
+            println!("  {}  {:>3}", week.week, week.episodes);
+        }
+    }
+}
+
+fn print_entry(entry: &Entry) {
+    let episodes = if entry.max_episodes > 0 {
+        format!("{}/{}", entry.watched_episodes, entry.max_episodes)
+    } else {
+        entry.watched_episodes.to_string()
+    };
+    println!(
+        "{:<9}  S{} E{:<3}  {:>7}  {}",
+        entry.status.to_string(),
+        entry.season,
+        entry.episode,
+        episodes,
+        entry.title
+    );
+}
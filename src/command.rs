@@ -0,0 +1,257 @@
+use crate::action::Action;
+use crate::app::App;
+use crate::consts::MAX_COUNT;
+use crate::sort::SortMode;
+use crate::Status;
+use std::path::Path;
+
+/// Commands typed after `:`, with their arguments and what they do, for help and completion.
+pub const COMMANDS: &[(&str, &str, &str)] = &[
+    ("add", "<title>", "add a new entry"),
+    ("rename", "<title>", "rename the selected entry"),
+    ("goto", "<title>", "select an entry by title"),
+    ("move", "<status>", "move the selected entry"),
+    ("set", "<field> <n>", "set season, episode, max or runtime"),
+    ("next", "[n]", "advance n episodes"),
+    ("filter", "[text]", "show matching titles, or all"),
+    ("sort", "[mode]", "sort the selected column"),
+    ("tag", "<tag>", "tag the selected entries"),
+    ("untag", "<tag>", "remove a tag from the selected entries"),
+    ("drop", "", "drop the selected entries, after asking"),
+    ("export", "[format] [path]", "export the watchlist"),
+    ("import", "<path>", "import a file (with preview)"),
+    ("dropped", "", "show dropped entries"),
+    ("episodes", "", "open the episodes popup"),
+    ("help", "", "toggle help"),
+    ("quit", "", "quit"),
+];
+
+const STATUSES: &[&str] = &["planning", "watching", "completed", "dropped"];
+const SET_FIELDS: &[&str] = &["season", "episode", "max", "runtime"];
+/// Export formats and the file extension each one is written with.
+const EXPORT_FORMATS: &[(&str, &str)] = &[
+    ("json", "json"),
+    ("csv", "csv"),
+    ("mal", "xml"),
+    ("markdown", "md"),
+    ("html", "html"),
+    ("ics", "ics"),
+];
+
+/// Turns a command line (without the `:`) into the actions it stands for.
+pub fn parse(line: &str, app: &App) -> Result<Vec<Action>, String> {
+    let line = line.trim();
+    let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim();
+    let name = resolve_name(name)?;
+
+    let action = match name {
+        "add" => Action::AddEntry(required(rest, "a title")?.to_string()),
+        "rename" => Action::EditTitle(required(rest, "a title")?.to_string()),
+        "goto" => {
+            let index = app
+                .find_entry(required(rest, "a title")?)
+                .map_err(|e| e.to_string())?;
+            Action::Select(index)
+        }
+        "move" => Action::MoveTo(parse_status(required(rest, "a status")?)?),
+        "set" => parse_set(rest)?,
+        "next" => {
+            let count = if rest.is_empty() {
+                1
+            } else {
+                parse_number(rest)?
+            };
+            if count > MAX_COUNT {
+                return Err(format!(
+                    "Can't advance more than {} episodes at once",
+                    MAX_COUNT
+                ));
+            }
+            if count == 0 {
+                return Ok(Vec::new());
+            }
+            Action::NextEpisode(count)
+        }
+        "filter" => Action::SetFilter(rest.to_string()),
+        "tag" => Action::Tag(parse_tag(rest)?),
+        "untag" => Action::Untag(parse_tag(rest)?),
+        "sort" if rest.is_empty() => Action::SetSort(None),
+        "sort" => Action::SetSort(Some(parse_sort_mode(rest)?)),
+        "drop" => Action::RequestRemove,
+        "export" => Action::Export(export_path(rest, app)?),
+        "import" => Action::LoadImport(required(rest, "a path")?.to_string()),
+        "dropped" => Action::OpenDropped,
+        "episodes" => Action::OpenEpisodes,
+        "help" => Action::ToggleHelp,
+        "quit" => Action::Quit,
+        other => unreachable!("'{}' is not in COMMANDS", other),
+    };
+    Ok(vec![action])
+}
+
+/// The command a name stands for: an exact name or a unique prefix like `mo` for `move`.
+fn resolve_name(name: &str) -> Result<&'static str, String> {
+    if name.is_empty() {
+        return Err("Type a command, e.g. :add Title".to_string());
+    }
+    let name = name.to_lowercase();
+    if name == "q" {
+        return Ok("quit");
+    }
+    let matches: Vec<&'static str> = COMMANDS
+        .iter()
+        .map(|(command, _, _)| *command)
+        .filter(|command| command.starts_with(&name))
+        .collect();
+    match matches.as_slice() {
+        [command] => Ok(command),
+        _ if matches.contains(&name.as_str()) => Ok(matches[0]),
+        [] => Err(format!("Unknown command '{}'", name)),
+        _ => Err(format!("'{}' could be {}", name, matches.join(", "))),
+    }
+}
+
+fn required<'a>(rest: &'a str, what: &str) -> Result<&'a str, String> {
+    if rest.is_empty() {
+        Err(format!("Expected {}", what))
+    } else {
+        Ok(rest)
+    }
+}
+
+fn parse_number(text: &str) -> Result<u32, String> {
+    text.parse()
+        .map_err(|_| format!("'{}' is not a number", text))
+}
+
+fn parse_status(text: &str) -> Result<Status, String> {
+    match text.to_lowercase().as_str() {
+        "planning" => Ok(Status::Planning),
+        "watching" => Ok(Status::Watching),
+        "completed" => Ok(Status::Completed),
+        "dropped" => Ok(Status::Dropped),
+        _ => Err(format!(
+            "Unknown status '{}', expected {}",
+            text,
+            STATUSES.join(", ")
+        )),
+    }
+}
+
+fn parse_tag(text: &str) -> Result<String, String> {
+    let tag = required(text, "a tag")?;
+    if tag.contains(char::is_whitespace) {
+        return Err(format!("Tags can't contain spaces: '{}'", tag));
+    }
+    Ok(tag.to_string())
+}
+
+fn parse_sort_mode(text: &str) -> Result<SortMode, String> {
+    SortMode::from_name(text).ok_or_else(|| {
+        let names: Vec<&str> = SortMode::ALL.iter().map(|mode| mode.name()).collect();
+        format!(
+            "Unknown sort mode '{}', expected {}",
+            text,
+            names.join(", ")
+        )
+    })
+}
+
+fn parse_set(rest: &str) -> Result<Action, String> {
+    let (field, value) = rest
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| "Expected a field and a number, e.g. :set season 3".to_string())?;
+    let value = parse_number(value.trim())?;
+    match field.to_lowercase().as_str() {
+        "season" => Ok(Action::SetPosition(Some(value), None)),
+        "episode" => Ok(Action::SetPosition(None, Some(value))),
+        "max" => Ok(Action::SetMaxEpisodes(value)),
+        "runtime" => Ok(Action::SetRuntime(value)),
+        _ => Err(format!(
+            "Unknown field '{}', expected {}",
+            field,
+            SET_FIELDS.join(", ")
+        )),
+    }
+}
+
+/// The file `:export [format] [path]` writes. The format picks the extension when the path
+/// has none, and the storage directory is used when no path is given.
+fn export_path(rest: &str, app: &App) -> Result<String, String> {
+    let (first, path) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let format = EXPORT_FORMATS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(first));
+    let (extension, path) = match format {
+        Some((_, extension)) => (Some(*extension), path.trim()),
+        None => (None, rest),
+    };
+
+    match (extension, path) {
+        (None, "") => Ok(app.default_export_path("json")),
+        (Some(extension), "") => Ok(app.default_export_path(extension)),
+        (None, path) => Ok(path.to_string()),
+        (Some(extension), path) => match Path::new(path).extension() {
+            None => Ok(format!("{}.{}", path, extension)),
+            Some(actual) if actual.eq_ignore_ascii_case(extension) => Ok(path.to_string()),
+            Some(actual) => Err(format!(
+                "{} ends in .{} but the format needs .{}",
+                path,
+                actual.to_string_lossy(),
+                extension
+            )),
+        },
+    }
+}
+
+/// Every way to complete the last word of `line`, as whole lines.
+pub fn complete(line: &str, app: &App) -> Vec<String> {
+    let (head, word) = match line.rfind(char::is_whitespace) {
+        Some(pos) if !line[..pos].trim().is_empty() => line.split_at(pos + 1),
+        _ => ("", line.trim_start()),
+    };
+
+    let candidates: Vec<String> = if head.is_empty() {
+        COMMANDS
+            .iter()
+            .map(|(command, _, _)| command.to_string())
+            .collect()
+    } else {
+        let command = head.split_whitespace().next().unwrap_or_default();
+        let args = head.split_whitespace().count();
+        match (resolve_name(command), args) {
+            (Ok("move"), 1) => STATUSES.iter().map(|s| s.to_string()).collect(),
+            (Ok("set"), 1) => SET_FIELDS.iter().map(|s| s.to_string()).collect(),
+            (Ok("export"), 1) => EXPORT_FORMATS.iter().map(|(f, _)| f.to_string()).collect(),
+            (Ok("tag" | "untag"), 1) => {
+                let mut tags: Vec<String> = app.entry.iter().flat_map(|e| e.tags.clone()).collect();
+                tags.sort();
+                tags.dedup();
+                tags
+            }
+            (Ok("sort"), 1) => SortMode::ALL.iter().map(|m| m.name().to_string()).collect(),
+            (Ok(name @ ("goto" | "rename")), _) => {
+                // Titles can contain spaces, so complete everything after the command.
+                let typed = line.trim_start()[command.len()..].trim_start();
+                return app
+                    .entry
+                    .iter()
+                    .filter(|entry| starts_with_ignore_case(&entry.title, typed))
+                    .map(|entry| format!("{} {}", name, entry.title))
+                    .collect();
+            }
+            _ => Vec::new(),
+        }
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| starts_with_ignore_case(candidate, word))
+        .map(|candidate| format!("{}{}", head, candidate))
+        .collect()
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.to_lowercase().starts_with(&prefix.to_lowercase())
+}
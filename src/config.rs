@@ -1,43 +1,588 @@
-use serde::Deserialize;
+use crate::consts;
+use crate::formats::csv::CsvMapping;
+use crate::import::ImportOptions;
+use crate::keymap::{KeyConfig, Keymap};
+use crate::theme::ThemeConfig;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CONFIG_FILE_NAME: &str = "config.yaml";
+const APP_DIR_NAME: &str = "senarai";
 
-#[derive(Deserialize, Clone)]
+pub const CONFIG_ENV: &str = "SENARAI_CONFIG";
+pub const STORAGE_PATH_ENV: &str = "SENARAI_STORAGE_PATH";
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
+    #[serde(default = "default_storage_path")]
     pub storage_path: String,
+    #[serde(default)]
+    pub import: ImportOptions,
+    #[serde(default)]
+    pub csv: CsvMapping,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub keys: KeyConfig,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let storage_path = env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| PathBuf::from("."));
-
         Self {
-            storage_path: storage_path.to_str().unwrap_or(".").to_string(),
+            storage_path: default_storage_path(),
+            import: ImportOptions::default(),
+            csv: CsvMapping::default(),
+            theme: ThemeConfig::default(),
+            keys: KeyConfig::default(),
         }
     }
 }
 
-pub fn load_config() -> Result<Config, String> {
-    let config_path = env::current_exe()
+impl Config {
+    /// Applies `--db` or `$SENARAI_STORAGE_PATH`, which take precedence over the config file.
+    pub fn apply_overrides(&mut self, args: &ConfigArgs) {
+        if let Some((storage_path, _)) = storage_override(args) {
+            self.storage_path = storage_path;
+        }
+    }
+}
+
+/// Command-line options that decide where the config and the watchlist are read from.
+#[derive(Args, Default, Clone)]
+pub struct ConfigArgs {
+    /// Config file to use instead of searching for one
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Directory holding the watchlist database, overriding storage_path
+    #[arg(long, global = true, value_name = "DIR")]
+    pub db: Option<PathBuf>,
+}
+
+/// The storage path given by `--db` or `$SENARAI_STORAGE_PATH`, with the option it came from.
+pub fn storage_override(args: &ConfigArgs) -> Option<(String, &'static str)> {
+    match &args.db {
+        Some(path) => Some((expand(&path.to_string_lossy()), "--db")),
+        None => env_var(STORAGE_PATH_ENV).map(|path| (expand(&path), "$SENARAI_STORAGE_PATH")),
+    }
+}
+
+pub fn load_config(args: &ConfigArgs) -> Result<Config, String> {
+    let mut config = match find_config_file(args)? {
+        Some(path) => {
+            let data = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let mut config = serde_yaml::from_str::<Config>(&data).map_err(|e| {
+                format!(
+                    "Failed to parse {}: {} (run `senarai config check` for details)",
+                    path.display(),
+                    e
+                )
+            })?;
+            config.storage_path = expand(&config.storage_path);
+            config
+        }
+        None => Config::default(),
+    };
+    config.apply_overrides(args);
+    Ok(config)
+}
+
+/// Why a config file was picked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigFileSource {
+    Flag,
+    Env,
+    XdgConfigHome,
+    ExecutableDir,
+}
+
+impl fmt::Display for ConfigFileSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ConfigFileSource::Flag => "--config",
+            ConfigFileSource::Env => "$SENARAI_CONFIG",
+            ConfigFileSource::XdgConfigHome => "$XDG_CONFIG_HOME",
+            ConfigFileSource::ExecutableDir => "executable directory",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The config file to read, searched in this order:
+///
+/// 1. `--config <path>`
+/// 2. `$SENARAI_CONFIG`
+/// 3. `$XDG_CONFIG_HOME/senarai/config.yaml` (`~/.config` if unset)
+/// 4. `config.yaml` next to the executable
+///
+/// An explicitly given file must exist; `None` means no file was found and defaults apply.
+pub fn find_config_file(args: &ConfigArgs) -> Result<Option<PathBuf>, String> {
+    Ok(locate_config_file(args)?.map(|(path, _)| path))
+}
+
+/// Like [`find_config_file`], also telling which step of the search found the file.
+pub fn locate_config_file(
+    args: &ConfigArgs,
+) -> Result<Option<(PathBuf, ConfigFileSource)>, String> {
+    let explicit = match (&args.config, env_var(CONFIG_ENV)) {
+        (Some(path), _) => Some((path.clone(), ConfigFileSource::Flag)),
+        (None, Some(path)) => Some((PathBuf::from(expand(&path)), ConfigFileSource::Env)),
+        (None, None) => None,
+    };
+    if let Some((path, source)) = explicit {
+        return if path.is_file() {
+            Ok(Some((path, source)))
+        } else {
+            Err(format!("Config file {} not found", path.display()))
+        };
+    }
+
+    let candidates = [
+        config_home().map(|dir| {
+            (
+                dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME),
+                ConfigFileSource::XdgConfigHome,
+            )
+        }),
+        exe_dir().map(|dir| (dir.join(CONFIG_FILE_NAME), ConfigFileSource::ExecutableDir)),
+    ];
+    Ok(candidates
+        .into_iter()
+        .flatten()
+        .find(|(path, _)| path.is_file()))
+}
+
+/// Where `senarai init` writes the config: `--config`, `$SENARAI_CONFIG` or the XDG location.
+pub fn init_config_path(args: &ConfigArgs) -> Option<PathBuf> {
+    args.config
+        .clone()
+        .or_else(|| env_var(CONFIG_ENV).map(|p| PathBuf::from(expand(&p))))
+        .or_else(|| config_home().map(|dir| dir.join(APP_DIR_NAME).join(CONFIG_FILE_NAME)))
+}
+
+/// `$XDG_DATA_HOME/senarai` (`~/.local/share/senarai` if unset), unless a database from an older
+/// version still lives next to the executable.
+pub fn default_storage_path() -> String {
+    if let Some(dir) = exe_dir().filter(|dir| dir.join(consts::DB_FILE_NAME).is_file()) {
+        return dir.to_string_lossy().to_string();
+    }
+
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .or_else(exe_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// `$XDG_CONFIG_HOME` (`~/.config` if unset).
+pub fn config_home() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    env_var(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| env_var("HOME").map(|home| Path::new(&home).join(fallback)))
+}
+
+fn exe_dir() -> Option<PathBuf> {
+    env::current_exe()
         .ok()
-        .and_then(|p| p.parent().map(|p| p.join(CONFIG_FILE_NAME)))
-        .unwrap_or_else(|| PathBuf::from(CONFIG_FILE_NAME));
-
-    if let Ok(data) = fs::read_to_string(config_path) {
-        match serde_yaml::from_str::<Config>(&data) {
-            Ok(mut config) => {
-                config.storage_path = shellexpand::tilde(&config.storage_path).to_string();
-                Ok(config)
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn expand(path: &str) -> String {
+    shellexpand::tilde(path).to_string()
+}
+
+/// Written by `senarai init`. Every setting is present with its default value.
+pub const DEFAULT_CONFIG: &str = r##"# Senarai configuration.
+#
+# Senarai uses the first config file it finds: --config <file>, $SENARAI_CONFIG,
+# $XDG_CONFIG_HOME/senarai/config.yaml, then config.yaml next to the executable.
+# Run `senarai config check` after editing and `senarai config show` to see the
+# values in effect.
+
+# Directory holding the watchlist database (senarai.db). Defaults to
+# $XDG_DATA_HOME/senarai. --db <dir> and $SENARAI_STORAGE_PATH take precedence.
+# storage_path: "~/.local/share/senarai"
+
+# How imported files are interpreted.
+import:
+  # Status for entries marked "on hold" or "paused":
+  # Planning, Watching, Completed or Dropped.
+  on_hold_status: Planning
+  # Extra status names mapped to senarai statuses, for example:
+  #   status_synonyms:
+  #     backlog: Planning
+  status_synonyms: {}
+
+# CSV headers holding each field when importing a CSV file
+# (see `senarai import --map`).
+csv:
+  title: title
+  status: status
+  season: season
+  episode: episode
+  watched: watched_episodes
+  max: max_episodes
+
+# Colors of the interface.
+theme:
+  # Built-in theme: dark, light, solarized or high-contrast.
+  name: dark
+  # Any element can be overridden with a color name (blue, light-yellow, ...),
+  # a 256-color index (33) or hex RGB ("#268bd2", quoted). Elements:
+  # text, highlight_bg, highlight_fg, border, title, footer_text,
+  # error_border, error_text, progress, progress_empty.
+  # highlight_bg: "#268bd2"
+  # highlight_fg: white
+
+# Key bindings.
+keys:
+  # Default keys to start from: default or vim (hjkl, g g/G, ? for help).
+  profile: default
+  # Keys for actions, replacing the profile's keys. Each action takes one key or
+  # a list, written like q, "#", space, enter, shift+up, ctrl+d or "g g" for keys
+  # pressed in order. The help popup lists the actions and their keys; see the
+  # README for every action name.
+  # next_season: ["ctrl+s", "#"]
+  # quit: [q, ctrl+c]
+"##;
+
+/// Dotted paths of every config key; `.*` accepts any key below its parent.
+const KNOWN_KEYS: &[&str] = &[
+    "storage_path",
+    "import",
+    "import.on_hold_status",
+    "import.status_synonyms",
+    "import.status_synonyms.*",
+    "csv",
+    "csv.title",
+    "csv.status",
+    "csv.season",
+    "csv.episode",
+    "csv.watched",
+    "csv.max",
+    "theme",
+    "theme.name",
+    "theme.text",
+    "theme.highlight_bg",
+    "theme.highlight_fg",
+    "theme.border",
+    "theme.title",
+    "theme.footer_text",
+    "theme.error_border",
+    "theme.error_text",
+    "theme.progress",
+    "theme.progress_empty",
+    "keys",
+    "keys.*",
+];
+
+/// A problem found by [`check_config`], with the 1-based line it refers to if known.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Validates the config file that would be used and the effective storage path.
+///
+/// Returns the file that was checked (if any) and every problem found.
+pub fn check_config(args: &ConfigArgs) -> Result<(Option<PathBuf>, Vec<Diagnostic>), String> {
+    let path = find_config_file(args)?;
+    let mut diagnostics = Vec::new();
+    let mut lines = HashMap::new();
+    let mut storage_path = default_storage_path();
+
+    if let Some(path) = &path {
+        let data = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        lines = key_lines(&data);
+
+        match serde_yaml::from_str::<serde_yaml::Value>(&data) {
+            Ok(value) => {
+                unknown_keys(&value, "", &lines, &mut diagnostics);
+                if let Some(path) = value.get("storage_path").and_then(|p| p.as_str()) {
+                    storage_path = expand(path);
+                }
+                match serde_yaml::from_str::<Config>(&data) {
+                    Ok(config) => {
+                        if let Err(conflict) = Keymap::new(&config.keys) {
+                            let (first, second) = conflict.actions;
+                            diagnostics.push(Diagnostic {
+                                line: lines
+                                    .get(&format!("keys.{}", second))
+                                    .or_else(|| lines.get(&format!("keys.{}", first)))
+                                    .copied(),
+                                message: conflict.to_string(),
+                            });
+                        }
+                    }
+                    Err(e) => diagnostics.push(Diagnostic {
+                        line: e.location().map(|l| l.line()),
+                        message: e.to_string(),
+                    }),
+                }
             }
-            Err(e) => Err(format!("Failed to parse config.yaml: {}", e)),
+            Err(e) => diagnostics.push(Diagnostic {
+                line: e.location().map(|l| l.line()),
+                message: e.to_string(),
+            }),
         }
-    } else {
-        Ok(Config::default())
     }
+
+    let storage_line = match storage_override(args) {
+        Some((path, _)) => {
+            storage_path = path;
+            None
+        }
+        None => lines.get("storage_path").copied(),
+    };
+    if let Some(message) = storage_problem(Path::new(&storage_path)) {
+        diagnostics.push(Diagnostic {
+            line: storage_line,
+            message,
+        });
+    }
+
+    diagnostics.sort_by_key(|d| d.line.unwrap_or(usize::MAX));
+    Ok((path, diagnostics))
+}
+
+/// Every effective setting as `(key, value, source)`, in the order `Config` serializes them.
+pub fn describe_config(args: &ConfigArgs) -> Result<Vec<(String, String, String)>, String> {
+    let located = locate_config_file(args)?;
+    let config = load_config(args)?;
+
+    let file_keys = match &located {
+        Some((path, _)) => fs::read_to_string(path)
+            .map(|data| key_lines(&data))
+            .unwrap_or_default(),
+        None => HashMap::new(),
+    };
+    let file_source = located
+        .as_ref()
+        .map(|(path, _)| path.display().to_string())
+        .unwrap_or_default();
+    let storage_source = storage_override(args).map(|(_, source)| source.to_string());
+
+    let value = serde_yaml::to_value(&config).map_err(|e| e.to_string())?;
+    let mut settings = Vec::new();
+    flatten(&value, "", &mut settings);
+
+    Ok(settings
+        .into_iter()
+        .map(|(key, value)| {
+            let source = match &storage_source {
+                Some(source) if key == "storage_path" => source.clone(),
+                _ if file_keys.contains_key(&key) => file_source.clone(),
+                _ => "default".to_string(),
+            };
+            (key, value, source)
+        })
+        .collect())
+}
+
+/// Maps the dotted path of every `key:` in block-style YAML to its 1-based line.
+fn key_lines(data: &str) -> HashMap<String, usize> {
+    let mut lines = HashMap::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+
+    for (i, line) in data.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let Some((key, _)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        let indent = line.len() - trimmed.len();
+
+        while parents.last().is_some_and(|(level, _)| *level >= indent) {
+            parents.pop();
+        }
+        let path = match parents.last() {
+            Some((_, parent)) => format!("{}.{}", parent, key),
+            None => key.to_string(),
+        };
+        lines.insert(path.clone(), i + 1);
+        parents.push((indent, path));
+    }
+
+    lines
+}
+
+fn is_known_key(path: &str) -> bool {
+    KNOWN_KEYS.contains(&path)
+        || path
+            .rsplit_once('.')
+            .is_some_and(|(parent, _)| KNOWN_KEYS.contains(&format!("{}.*", parent).as_str()))
+}
+
+fn unknown_keys(
+    value: &serde_yaml::Value,
+    prefix: &str,
+    lines: &HashMap<String, usize>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(mapping) = value.as_mapping() else {
+        return;
+    };
+    for (key, child) in mapping {
+        let key = match key {
+            serde_yaml::Value::String(key) => key.clone(),
+            other => serde_yaml::to_string(other)
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        };
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        if !is_known_key(&path) {
+            let mut message = format!("unknown key '{}'", path);
+            if let Some(suggestion) = suggest_key(&path) {
+                message.push_str(&format!(", did you mean '{}'?", suggestion));
+            }
+            diagnostics.push(Diagnostic {
+                line: lines.get(&path).copied(),
+                message,
+            });
+            continue;
+        }
+        unknown_keys(child, &path, lines, diagnostics);
+    }
+}
+
+/// A known key at the same level that is at most two edits away from `path`.
+fn suggest_key(path: &str) -> Option<&'static str> {
+    let (parent, key) = path.rsplit_once('.').unwrap_or(("", path));
+    KNOWN_KEYS
+        .iter()
+        .filter(|known| !known.ends_with('*'))
+        .filter(|known| known.rsplit_once('.').map_or("", |(p, _)| p) == parent)
+        .map(|known| {
+            let name = known.rsplit_once('.').map_or(*known, |(_, k)| k);
+            (edit_distance(key, name), *known)
+        })
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(row[j + 1])
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// Why the watchlist can't be stored at `path`, if it can't.
+fn storage_problem(path: &Path) -> Option<String> {
+    let shown = path.display();
+    if path.exists() {
+        if !path.is_dir() {
+            return Some(format!("storage_path {} is not a directory", shown));
+        }
+        if let Err(e) = fs::read_dir(path) {
+            return Some(format!("storage_path {} is not readable: {}", shown, e));
+        }
+        let probe = path.join(".senarai-check");
+        return match fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = fs::remove_file(probe);
+                None
+            }
+            Err(e) => Some(format!("storage_path {} is not writable: {}", shown, e)),
+        };
+    }
+
+    match path.ancestors().skip(1).find(|p| p.exists()) {
+        Some(parent) if !parent.is_dir() => Some(format!(
+            "storage_path {} can't be created: {} is not a directory",
+            shown,
+            parent.display()
+        )),
+        Some(parent) if parent.metadata().is_ok_and(|m| m.permissions().readonly()) => {
+            Some(format!(
+                "storage_path {} can't be created: {} is read-only",
+                shown,
+                parent.display()
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn flatten(value: &serde_yaml::Value, prefix: &str, out: &mut Vec<(String, String)>) {
+    use serde_yaml::Value;
+
+    let text = match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            let mut children: Vec<_> = mapping.iter().collect();
+            // Free-form sections come from hash maps; keep their order stable.
+            if is_known_key(&format!("{}.*", prefix)) {
+                children.sort_by_key(|(key, _)| key.as_str().map(str::to_string));
+            }
+            for (key, child) in children {
+                let key = key.as_str().unwrap_or_default();
+                let path = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(child, &path, out);
+            }
+            return;
+        }
+        Value::Mapping(_) => "{}".to_string(),
+        Value::Sequence(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => s.clone(),
+                    other => serde_yaml::to_string(other)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                })
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    };
+    out.push((prefix.to_string(), text));
 }
@@ -1,28 +1,33 @@
-use ratatui::prelude::*;
-
-pub const TEXT_COLOR: Color = Color::Blue;
-pub const HIGHLIGHT_BG: Color = Color::Blue;
-pub const HIGHLIGHT_FG: Color = Color::Gray;
-pub const BORDER_COLOR: Color = Color::Blue;
-pub const TITLE_COLOR: Color = Color::LightYellow;
-pub const FOOTER_TEXT_COLOR: Color = Color::DarkGray;
-pub const ERROR_BORDER_COLOR: Color = Color::Red;
-pub const ERROR_TEXT_COLOR: Color = Color::Red;
-
 pub const COLUMN_1_WIDTH: u16 = 33;
 pub const COLUMN_2_WIDTH: u16 = 34;
 pub const COLUMN_3_WIDTH: u16 = 33;
 
-pub const HELP_POPUP_WIDTH: u16 = 80;
-pub const HELP_POPUP_HEIGHT: u16 = 50;
-pub const TITLE_POPUP_WIDTH: u16 = 60;
-pub const TITLE_POPUP_HEIGHT: u16 = 10;
-pub const CONFIRMATION_POPUP_WIDTH: u16 = 50;
-pub const CONFIRMATION_POPUP_HEIGHT: u16 = 20;
-pub const DROPPED_POPUP_WIDTH: u16 = 80;
-pub const DROPPED_POPUP_HEIGHT: u16 = 50;
+/// Widest the full title popup gets before wrapping.
+pub const TITLE_POPUP_MAX_WIDTH: u16 = 60;
+pub const EPISODES_POPUP_WIDTH: u16 = 50;
+pub const STATS_POPUP_WIDTH: u16 = 60;
+/// Titles listed for the day picked in the activity calendar.
+pub const ACTIVITY_DAY_ROWS: usize = 5;
+
+/// Below this width the board shows one column at a time, with tabs to switch between them.
+pub const TABBED_BOARD_WIDTH: u16 = 90;
+/// Smallest terminal the UI is drawn in; smaller ones only get a notice.
+pub const MIN_TERMINAL_WIDTH: u16 = 30;
+pub const MIN_TERMINAL_HEIGHT: u16 = 10;
+
+/// Terminals at least this wide show the detail pane beside the board instead of below it.
+pub const DETAIL_PANE_SIDE_MIN_WIDTH: u16 = 130;
+pub const DETAIL_PANE_WIDTH: u16 = 40;
+pub const DETAIL_PANE_HEIGHT: u16 = 12;
 
 pub const PADDING: usize = 2;
+/// Rows kept visible above and below the selection while scrolling a list.
+pub const SCROLL_OFF: usize = 2;
+/// Rows one step of the mouse wheel scrolls.
+pub const MOUSE_SCROLL_ROWS: isize = 3;
 
 pub const ERROR_POPUP_DURATION: u64 = 3;
+pub const MAX_COMMAND_HISTORY: usize = 100;
+/// Largest count prefix for keys, and largest `:next` count.
+pub const MAX_COUNT: u32 = 999;
 pub const DB_FILE_NAME: &str = "senarai.db";
@@ -1,31 +1,98 @@
+use crate::sort::SortMode;
 use crate::{config::Config, consts, Entry, Status};
+use chrono::{Local, NaiveDateTime, SubsecRound};
 use rusqlite::types::Type;
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, Result, Row};
 use std::path::Path;
 use uuid::Uuid;
 
+const ENTRY_COLUMNS: &str =
+    "id, title, status, season, episode, watched_episodes, max_episodes, runtime_minutes, season_runtimes, next_air_date, watch_sessions, updated_at, tags, watch_log";
+
+/// How `updated_at` is stored.
+pub const UPDATED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn entry_from_row(row: &Row) -> Result<Entry> {
+    let status_str: String = row.get(2)?;
+    let status = Status::from(status_str);
+    let season_runtimes: String = row.get(8)?;
+    let next_air_date: Option<String> = row.get(9)?;
+    let watch_sessions: String = row.get(10)?;
+    let updated_at: Option<String> = row.get(11)?;
+    let tags: String = row.get(12)?;
+    let watch_log: String = row.get(13)?;
+    Ok(Entry {
+        id: Uuid::parse_str(&row.get::<_, String>(0)?)
+            .map_err(|_e| rusqlite::Error::InvalidColumnType(0, "uuid".to_string(), Type::Text))?,
+        title: row.get(1)?,
+        status,
+        season: row.get(3)?,
+        episode: row.get(4)?,
+        watched_episodes: row.get(5)?,
+        max_episodes: row.get(6)?,
+        runtime_minutes: row.get(7)?,
+        season_runtimes: serde_json::from_str(&season_runtimes).map_err(|_e| {
+            rusqlite::Error::InvalidColumnType(8, "season_runtimes".to_string(), Type::Text)
+        })?,
+        next_air_date: next_air_date
+            .map(|date| date.parse())
+            .transpose()
+            .map_err(|_e| {
+                rusqlite::Error::InvalidColumnType(9, "next_air_date".to_string(), Type::Text)
+            })?,
+        watch_sessions: serde_json::from_str(&watch_sessions).map_err(|_e| {
+            rusqlite::Error::InvalidColumnType(10, "watch_sessions".to_string(), Type::Text)
+        })?,
+        updated_at: updated_at
+            .map(|time| NaiveDateTime::parse_from_str(&time, UPDATED_AT_FORMAT))
+            .transpose()
+            .map_err(|_e| {
+                rusqlite::Error::InvalidColumnType(11, "updated_at".to_string(), Type::Text)
+            })?,
+        tags: serde_json::from_str(&tags)
+            .map_err(|_e| rusqlite::Error::InvalidColumnType(12, "tags".to_string(), Type::Text))?,
+        watch_log: serde_json::from_str(&watch_log).map_err(|_e| {
+            rusqlite::Error::InvalidColumnType(13, "watch_log".to_string(), Type::Text)
+        })?,
+    })
+}
+
+fn season_runtimes_to_json(entry: &Entry) -> String {
+    serde_json::to_string(&entry.season_runtimes).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn watch_sessions_to_json(entry: &Entry) -> String {
+    serde_json::to_string(&entry.watch_sessions).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn tags_to_json(entry: &Entry) -> String {
+    serde_json::to_string(&entry.tags).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn watch_log_to_json(entry: &Entry) -> String {
+    serde_json::to_string(&entry.watch_log).unwrap_or_else(|_| "{}".to_string())
+}
+
+fn updated_at_to_text(entry: &Entry) -> Option<String> {
+    entry
+        .updated_at
+        .map(|time| time.format(UPDATED_AT_FORMAT).to_string())
+}
+
+/// The current local time as `updated_at` stores it, without fractions of a second.
+pub fn now() -> NaiveDateTime {
+    Local::now().naive_local().trunc_subsecs(0)
+}
+
 pub fn load_entry(config: &Config) -> Result<Vec<Entry>> {
     let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
     let conn = Connection::open(db_path)?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, title, status, season, episode, watched_episodes, max_episodes FROM entries ORDER BY ordering ASC",
-    )?;
-    let entries_iter = stmt.query_map([], |row| {
-        let status_str: String = row.get(2)?;
-        let status = Status::from(status_str);
-        Ok(Entry {
-            id: Uuid::parse_str(&row.get::<_, String>(0)?).map_err(|_e| {
-                rusqlite::Error::InvalidColumnType(0, "uuid".to_string(), Type::Text)
-            })?,
-            title: row.get(1)?,
-            status,
-            season: row.get(3)?,
-            episode: row.get(4)?,
-            watched_episodes: row.get(5)?,
-            max_episodes: row.get(6)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM entries ORDER BY ordering ASC",
+        ENTRY_COLUMNS
+    ))?;
+    let entries_iter = stmt.query_map([], entry_from_row)?;
 
     let mut entries = Vec::new();
     for entry in entries_iter {
@@ -38,24 +105,11 @@ pub fn get_entry_by_title(title: &str, config: &Config) -> Result<Option<Entry>>
     let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
     let conn = Connection::open(db_path)?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, title, status, season, episode, watched_episodes, max_episodes FROM entries WHERE title = ?1",
-    )?;
-    let mut entries_iter = stmt.query_map([title], |row| {
-        let status_str: String = row.get(2)?;
-        let status = Status::from(status_str);
-        Ok(Entry {
-            id: Uuid::parse_str(&row.get::<_, String>(0)?).map_err(|_e| {
-                rusqlite::Error::InvalidColumnType(0, "uuid".to_string(), Type::Text)
-            })?,
-            title: row.get(1)?,
-            status,
-            season: row.get(3)?,
-            episode: row.get(4)?,
-            watched_episodes: row.get(5)?,
-            max_episodes: row.get(6)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM entries WHERE title = ?1",
+        ENTRY_COLUMNS
+    ))?;
+    let mut entries_iter = stmt.query_map([title], entry_from_row)?;
 
     if let Some(entry_result) = entries_iter.next() {
         Ok(Some(entry_result?))
@@ -73,7 +127,7 @@ pub fn add_entry(entry: &Entry, config: &Config) -> Result<()> {
     })?;
 
     conn.execute(
-        "INSERT INTO entries (id, title, status, season, episode, watched_episodes, max_episodes, ordering) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        "INSERT INTO entries (id, title, status, season, episode, watched_episodes, max_episodes, runtime_minutes, season_runtimes, next_air_date, watch_sessions, updated_at, tags, watch_log, ordering) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
         (
             &entry.id.to_string(),
             &entry.title,
@@ -82,6 +136,13 @@ pub fn add_entry(entry: &Entry, config: &Config) -> Result<()> {
             &entry.episode,
             &entry.watched_episodes,
             &entry.max_episodes,
+            &entry.runtime_minutes,
+            season_runtimes_to_json(entry),
+            entry.next_air_date.map(|date| date.to_string()),
+            watch_sessions_to_json(entry),
+            updated_at_to_text(entry),
+            tags_to_json(entry),
+            watch_log_to_json(entry),
             max_ordering + 1,
         ),
     )?;
@@ -89,6 +150,7 @@ pub fn add_entry(entry: &Entry, config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Saves `entries` in their current order.
 pub fn update_all_entries(entries: &[Entry], config: &Config) -> Result<()> {
     let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
     let mut conn = Connection::open(db_path)?;
@@ -96,13 +158,21 @@ pub fn update_all_entries(entries: &[Entry], config: &Config) -> Result<()> {
 
     for (i, entry) in entries.iter().enumerate() {
         tx.execute(
-            "UPDATE entries SET status = ?1, season = ?2, episode = ?3, watched_episodes = ?4, max_episodes = ?5, ordering = ?6 WHERE id = ?7",
+            "UPDATE entries SET title = ?1, status = ?2, season = ?3, episode = ?4, watched_episodes = ?5, max_episodes = ?6, runtime_minutes = ?7, season_runtimes = ?8, next_air_date = ?9, watch_sessions = ?10, updated_at = ?11, tags = ?12, watch_log = ?13, ordering = ?14 WHERE id = ?15",
             (
+                &entry.title,
                 &entry.status.to_string(),
                 &entry.season,
                 &entry.episode,
                 &entry.watched_episodes,
                 &entry.max_episodes,
+                &entry.runtime_minutes,
+                season_runtimes_to_json(entry),
+                entry.next_air_date.map(|date| date.to_string()),
+                watch_sessions_to_json(entry),
+                updated_at_to_text(entry),
+                tags_to_json(entry),
+                watch_log_to_json(entry),
                 i as i64,
                 &entry.id.to_string(),
             ),
@@ -113,6 +183,40 @@ pub fn update_all_entries(entries: &[Entry], config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Replaces every stored entry with `entries`, keeping their order, in one transaction.
+pub fn replace_all_entries(entries: &[Entry], config: &Config) -> Result<()> {
+    let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
+    let mut conn = Connection::open(db_path)?;
+    let tx = conn.transaction()?;
+
+    tx.execute("DELETE FROM entries", ())?;
+    for (i, entry) in entries.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO entries (id, title, status, season, episode, watched_episodes, max_episodes, runtime_minutes, season_runtimes, next_air_date, watch_sessions, updated_at, tags, watch_log, ordering) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            (
+                &entry.id.to_string(),
+                &entry.title,
+                &entry.status.to_string(),
+                &entry.season,
+                &entry.episode,
+                &entry.watched_episodes,
+                &entry.max_episodes,
+                &entry.runtime_minutes,
+                season_runtimes_to_json(entry),
+                entry.next_air_date.map(|date| date.to_string()),
+                watch_sessions_to_json(entry),
+                updated_at_to_text(entry),
+                tags_to_json(entry),
+                watch_log_to_json(entry),
+                i as i64,
+            ),
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
 pub fn delete_entry(id: &Uuid, config: &Config) -> Result<()> {
     let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
     let conn = Connection::open(db_path)?;
@@ -122,12 +226,26 @@ pub fn delete_entry(id: &Uuid, config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Deletes several entries in one transaction, so either all or none are gone.
+pub fn delete_entries(ids: &[Uuid], config: &Config) -> Result<()> {
+    let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
+    let mut conn = Connection::open(db_path)?;
+    let tx = conn.transaction()?;
+
+    for id in ids {
+        tx.execute("DELETE FROM entries WHERE id = ?1", [id.to_string()])?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
 fn add_watched_episodes_column_if_not_exists(conn: &Connection) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(entries)")?;
     let column_exists = stmt
         .query_map([], |row| row.get(1))?
         .any(|col_name_result| {
-            col_name_result.map_or(false, |col_name: String| col_name == "watched_episodes")
+            col_name_result.is_ok_and(|col_name: String| col_name == "watched_episodes")
         });
 
     if !column_exists {
@@ -145,7 +263,7 @@ fn add_ordering_column_if_not_exists(conn: &Connection) -> Result<()> {
     let column_exists = stmt
         .query_map([], |row| row.get(1))?
         .any(|col_name_result| {
-            col_name_result.map_or(false, |col_name: String| col_name == "ordering")
+            col_name_result.is_ok_and(|col_name: String| col_name == "ordering")
         });
 
     if !column_exists {
@@ -171,7 +289,7 @@ fn add_max_episodes_column_if_not_exists(conn: &Connection) -> Result<()> {
     let column_exists = stmt
         .query_map([], |row| row.get(1))?
         .any(|col_name_result| {
-            col_name_result.map_or(false, |col_name: String| col_name == "max_episodes")
+            col_name_result.is_ok_and(|col_name: String| col_name == "max_episodes")
         });
 
     if !column_exists {
@@ -184,6 +302,82 @@ fn add_max_episodes_column_if_not_exists(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+fn add_runtime_columns_if_not_exist(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(entries)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))?
+        .collect::<Result<_>>()?;
+
+    if !columns.iter().any(|c| c == "runtime_minutes") {
+        conn.execute(
+            "ALTER TABLE entries ADD COLUMN runtime_minutes INTEGER NOT NULL DEFAULT 0",
+            (),
+        )?;
+    }
+    if !columns.iter().any(|c| c == "season_runtimes") {
+        conn.execute(
+            "ALTER TABLE entries ADD COLUMN season_runtimes TEXT NOT NULL DEFAULT '{}'",
+            (),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn add_updated_at_and_tags_columns_if_not_exist(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(entries)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))?
+        .collect::<Result<_>>()?;
+
+    if !columns.iter().any(|c| c == "updated_at") {
+        conn.execute("ALTER TABLE entries ADD COLUMN updated_at TEXT", ())?;
+    }
+    if !columns.iter().any(|c| c == "tags") {
+        conn.execute(
+            "ALTER TABLE entries ADD COLUMN tags TEXT NOT NULL DEFAULT '[]'",
+            (),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn add_schedule_columns_if_not_exist(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(entries)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))?
+        .collect::<Result<_>>()?;
+
+    if !columns.iter().any(|c| c == "next_air_date") {
+        conn.execute("ALTER TABLE entries ADD COLUMN next_air_date TEXT", ())?;
+    }
+    if !columns.iter().any(|c| c == "watch_sessions") {
+        conn.execute(
+            "ALTER TABLE entries ADD COLUMN watch_sessions TEXT NOT NULL DEFAULT '[]'",
+            (),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn add_watch_log_column_if_not_exists(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(entries)")?;
+    let columns: Vec<String> = stmt
+        .query_map([], |row| row.get(1))?
+        .collect::<Result<_>>()?;
+
+    if !columns.iter().any(|c| c == "watch_log") {
+        conn.execute(
+            "ALTER TABLE entries ADD COLUMN watch_log TEXT NOT NULL DEFAULT '{}'",
+            (),
+        )?;
+    }
+
+    Ok(())
+}
+
 pub fn init_db(config: &Config) -> Result<()> {
     let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
 
@@ -209,14 +403,66 @@ pub fn init_db(config: &Config) -> Result<()> {
             episode INTEGER NOT NULL,
             watched_episodes INTEGER NOT NULL DEFAULT 0,
             max_episodes INTEGER NOT NULL DEFAULT 0,
+            runtime_minutes INTEGER NOT NULL DEFAULT 0,
+            season_runtimes TEXT NOT NULL DEFAULT '{}',
+            next_air_date TEXT,
+            watch_sessions TEXT NOT NULL DEFAULT '[]',
+            updated_at TEXT,
+            tags TEXT NOT NULL DEFAULT '[]',
+            watch_log TEXT NOT NULL DEFAULT '{}',
             ordering INTEGER
         )",
         (),
     )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS column_sort (
+            status TEXT PRIMARY KEY,
+            mode TEXT NOT NULL
+        )",
+        (),
+    )?;
 
     add_watched_episodes_column_if_not_exists(&conn)?;
     add_max_episodes_column_if_not_exists(&conn)?;
     add_ordering_column_if_not_exists(&conn)?;
+    add_runtime_columns_if_not_exist(&conn)?;
+    add_schedule_columns_if_not_exist(&conn)?;
+    add_updated_at_and_tags_columns_if_not_exist(&conn)?;
+    add_watch_log_column_if_not_exists(&conn)?;
+
+    Ok(())
+}
+
+/// The sort mode saved for each column; columns without one are sorted manually.
+pub fn load_sort_modes(config: &Config) -> Result<Vec<(Status, SortMode)>> {
+    let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
+    let conn = Connection::open(db_path)?;
+
+    let mut stmt = conn.prepare("SELECT status, mode FROM column_sort")?;
+    let rows = stmt.query_map([], |row| {
+        let status: String = row.get(0)?;
+        let mode: String = row.get(1)?;
+        Ok((Status::from(status), SortMode::from_name(&mode)))
+    })?;
+
+    let mut modes = Vec::new();
+    for row in rows {
+        if let (status, Some(mode)) = row? {
+            modes.push((status, mode));
+        }
+    }
+    Ok(modes)
+}
+
+pub fn set_sort_mode(status: Status, mode: SortMode, config: &Config) -> Result<()> {
+    let db_path = Path::new(&config.storage_path).join(consts::DB_FILE_NAME);
+    let conn = Connection::open(db_path)?;
+
+    conn.execute(
+        "INSERT INTO column_sort (status, mode) VALUES (?1, ?2)
+         ON CONFLICT(status) DO UPDATE SET mode = excluded.mode",
+        (status.to_string(), mode.name()),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,349 @@
+use crate::database::UPDATED_AT_FORMAT;
+use crate::formats::{has_extension, parse_status};
+use crate::import::{ImportOptions, Importer, ParsedImport};
+use crate::{config::Config, Entry, Status};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Format of watch sessions in the `watch_sessions` column, separated by `;`.
+const SESSION_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+const HEADERS: [&str; 14] = [
+    "id",
+    "title",
+    "status",
+    "season",
+    "episode",
+    "watched_episodes",
+    "max_episodes",
+    "runtime_minutes",
+    "season_runtimes",
+    "next_air_date",
+    "watch_sessions",
+    "updated_at",
+    "tags",
+    "watch_log",
+];
+
+/// Which CSV header holds each entry field.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct CsvMapping {
+    pub title: String,
+    pub status: String,
+    pub season: String,
+    pub episode: String,
+    pub watched: String,
+    pub max: String,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        Self {
+            title: "title".to_string(),
+            status: "status".to_string(),
+            season: "season".to_string(),
+            episode: "episode".to_string(),
+            watched: "watched_episodes".to_string(),
+            max: "max_episodes".to_string(),
+        }
+    }
+}
+
+impl CsvMapping {
+    /// Applies a `field=Header` override as given on the command line.
+    pub fn set_column(&mut self, assignment: &str) -> Result<(), String> {
+        let (field, header) = assignment.split_once('=').ok_or_else(|| {
+            format!(
+                "Invalid column mapping '{}', expected field=Header",
+                assignment
+            )
+        })?;
+        let header = header.trim().to_string();
+        match field.trim() {
+            "title" => self.title = header,
+            "status" => self.status = header,
+            "season" => self.season = header,
+            "episode" => self.episode = header,
+            "watched" => self.watched = header,
+            "max" => self.max = header,
+            other => return Err(format!("Unknown CSV field '{}'", other)),
+        }
+        Ok(())
+    }
+}
+
+pub fn to_string(entries: &[Entry]) -> Result<String, String> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(HEADERS)
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+
+    for entry in entries {
+        let season_runtimes = entry
+            .season_runtimes
+            .iter()
+            .map(|(season, minutes)| format!("{}:{}", season, minutes))
+            .collect::<Vec<_>>()
+            .join(";");
+        let watch_sessions = entry
+            .watch_sessions
+            .iter()
+            .map(|session| session.format(SESSION_FORMAT).to_string())
+            .collect::<Vec<_>>()
+            .join(";");
+        // Days map to episode counts, written as JSON like the database stores them.
+        let watch_log = if entry.watch_log.is_empty() {
+            String::new()
+        } else {
+            serde_json::to_string(&entry.watch_log)
+                .map_err(|e| format!("Failed to write CSV: {}", e))?
+        };
+        writer
+            .write_record([
+                entry.id.to_string(),
+                entry.title.clone(),
+                entry.status.to_string(),
+                entry.season.to_string(),
+                entry.episode.to_string(),
+                entry.watched_episodes.to_string(),
+                entry.max_episodes.to_string(),
+                entry.runtime_minutes.to_string(),
+                season_runtimes,
+                entry
+                    .next_air_date
+                    .map(|date| date.to_string())
+                    .unwrap_or_default(),
+                watch_sessions,
+                entry
+                    .updated_at
+                    .map(|time| time.format(UPDATED_AT_FORMAT).to_string())
+                    .unwrap_or_default(),
+                entry.tags.join(";"),
+                watch_log,
+            ])
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    }
+
+    let data = writer
+        .into_inner()
+        .map_err(|e| format!("Failed to write CSV: {}", e))?;
+    String::from_utf8(data).map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+/// Header positions resolved from a `CsvMapping`.
+struct Columns {
+    title: usize,
+    status: Option<usize>,
+    season: Option<usize>,
+    episode: Option<usize>,
+    watched: Option<usize>,
+    max: Option<usize>,
+    id: Option<usize>,
+    runtime: Option<usize>,
+    season_runtimes: Option<usize>,
+    next_air_date: Option<usize>,
+    watch_sessions: Option<usize>,
+    updated_at: Option<usize>,
+    tags: Option<usize>,
+    watch_log: Option<usize>,
+}
+
+impl Columns {
+    fn resolve(headers: &::csv::StringRecord, mapping: &CsvMapping) -> Result<Self, String> {
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(name.trim()))
+        };
+
+        Ok(Self {
+            title: column(&mapping.title)
+                .ok_or_else(|| format!("CSV has no title column '{}'", mapping.title))?,
+            status: column(&mapping.status),
+            season: column(&mapping.season),
+            episode: column(&mapping.episode),
+            watched: column(&mapping.watched),
+            max: column(&mapping.max),
+            id: column("id"),
+            runtime: column("runtime_minutes"),
+            season_runtimes: column("season_runtimes"),
+            next_air_date: column("next_air_date"),
+            watch_sessions: column("watch_sessions"),
+            updated_at: column("updated_at"),
+            tags: column("tags"),
+            watch_log: column("watch_log"),
+        })
+    }
+}
+
+pub fn from_str(
+    data: &str,
+    mapping: &CsvMapping,
+    options: &ImportOptions,
+) -> Result<ParsedImport, String> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(data.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .clone();
+    let columns = Columns::resolve(&headers, mapping)?;
+
+    let mut parsed = ParsedImport::default();
+
+    for (i, record) in reader.records().enumerate() {
+        // The header is line 1.
+        let line = i + 2;
+        let result = match record {
+            Ok(record) => parse_record(&record, &columns, options).map_err(|e| {
+                match record.get(columns.title).filter(|t| !t.is_empty()) {
+                    Some(title) => format!("'{}': {}", title, e),
+                    None => e,
+                }
+            }),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(entry) => parsed.entries.push(entry),
+            Err(reason) => parsed.rejected.push((format!("row {}", line), reason)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_record(
+    record: &::csv::StringRecord,
+    columns: &Columns,
+    options: &ImportOptions,
+) -> Result<Entry, String> {
+    let field = |col: Option<usize>| col.and_then(|c| record.get(c)).unwrap_or("");
+    let number = |name: &str, col: Option<usize>, default: u32| match field(col) {
+        "" => Ok(default),
+        value => value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid {} '{}'", name, value)),
+    };
+
+    let title = field(Some(columns.title));
+    if title.is_empty() {
+        return Err("missing title".to_string());
+    }
+
+    let status = match field(columns.status) {
+        "" => Status::Planning,
+        value => {
+            parse_status(value, options).ok_or_else(|| format!("unknown status '{}'", value))?
+        }
+    };
+
+    let id = match field(columns.id) {
+        "" => Uuid::new_v4(),
+        value => Uuid::parse_str(value).map_err(|_| format!("invalid id '{}'", value))?,
+    };
+
+    let mut season_runtimes = BTreeMap::new();
+    for pair in field(columns.season_runtimes)
+        .split(';')
+        .filter(|p| !p.trim().is_empty())
+    {
+        let (season, minutes) = pair
+            .split_once(':')
+            .and_then(|(s, m)| Some((s.trim().parse().ok()?, m.trim().parse().ok()?)))
+            .ok_or_else(|| format!("invalid season runtime '{}'", pair))?;
+        season_runtimes.insert(season, minutes);
+    }
+
+    let next_air_date = match field(columns.next_air_date) {
+        "" => None,
+        value => Some(
+            value
+                .parse::<NaiveDate>()
+                .map_err(|_| format!("invalid air date '{}'", value))?,
+        ),
+    };
+
+    let mut watch_sessions = Vec::new();
+    for session in field(columns.watch_sessions)
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        watch_sessions.push(
+            NaiveDateTime::parse_from_str(session, SESSION_FORMAT)
+                .map_err(|_| format!("invalid watch session '{}'", session))?,
+        );
+    }
+
+    let updated_at = match field(columns.updated_at) {
+        "" => None,
+        value => Some(
+            NaiveDateTime::parse_from_str(value, UPDATED_AT_FORMAT)
+                .map_err(|_| format!("invalid update time '{}'", value))?,
+        ),
+    };
+
+    // Stored like `Entry::add_tag` keeps them: lowercase, sorted and without duplicates.
+    let mut tags: Vec<String> = field(columns.tags)
+        .split(';')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    let watch_log = match field(columns.watch_log) {
+        "" => BTreeMap::new(),
+        value => {
+            serde_json::from_str(value).map_err(|_| format!("invalid watch log '{}'", value))?
+        }
+    };
+
+    Ok(Entry {
+        id,
+        title: title.to_string(),
+        status,
+        season: number("season", columns.season, 1)?,
+        episode: number("episode", columns.episode, 0)?,
+        watched_episodes: number("watched episodes", columns.watched, 0)?,
+        max_episodes: number("max episodes", columns.max, 0)?,
+        runtime_minutes: number("runtime", columns.runtime, 0)?,
+        season_runtimes,
+        next_air_date,
+        watch_sessions,
+        updated_at,
+        tags,
+        watch_log,
+    })
+}
+
+pub fn export(entries: &[Entry], path: &Path) -> Result<(), String> {
+    fs::write(path, to_string(entries)?)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Generic CSV using the configured column mapping.
+pub struct CsvImporter;
+
+impl Importer for CsvImporter {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn detect(&self, path: &Path, _data: &str) -> bool {
+        has_extension(path, "csv")
+    }
+
+    fn parse(&self, _path: &Path, data: &str, config: &Config) -> Result<ParsedImport, String> {
+        from_str(data, &config.csv, &config.import)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Entry, Status};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use std::fs;
+use std::path::Path;
+
+/// How many upcoming episodes of one entry are put in the calendar.
+const MAX_UPCOMING_EPISODES: u32 = 26;
+
+/// Length of a watch session when the entry has no runtime.
+const DEFAULT_SESSION_MINUTES: u32 = 60;
+
+/// Renders an iCalendar file with the upcoming episodes of Watching entries and every planned
+/// watch session from `now` on.
+///
+/// Episodes are all-day events, assumed to air weekly from `next_air_date`. Sessions use floating
+/// local time. UIDs are derived from the entry id and the episode or session time, so importing a
+/// newer export updates events instead of duplicating them.
+pub fn to_string(entries: &[Entry], now: NaiveDateTime) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//senarai//watchlist//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Senarai".to_string(),
+    ];
+
+    for entry in entries.iter().filter(|e| e.status == Status::Watching) {
+        for (season, episode, date) in upcoming_episodes(entry, now) {
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}-s{}e{}@senarai", entry.id, season, episode),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+                format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (date + Duration::days(1)).format("%Y%m%d")
+                ),
+                format!(
+                    "SUMMARY:{}",
+                    escape_text(&format!("{} S{}E{}", entry.title, season, episode))
+                ),
+                "TRANSP:TRANSPARENT".to_string(),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+    }
+
+    for entry in entries.iter().filter(|e| e.status != Status::Dropped) {
+        let minutes = match entry.runtime_for_season(entry.season) {
+            0 => DEFAULT_SESSION_MINUTES,
+            minutes => minutes,
+        };
+        for session in entry.watch_sessions.iter().filter(|s| **s >= now) {
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!(
+                    "UID:{}-session-{}@senarai",
+                    entry.id,
+                    session.format("%Y%m%dT%H%M")
+                ),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART:{}", session.format("%Y%m%dT%H%M%S")),
+                format!("DURATION:PT{}M", minutes),
+                format!("SUMMARY:{}", escape_text(&format!("Watch {}", entry.title))),
+                format!(
+                    "DESCRIPTION:{}",
+                    escape_text(&format!("Next up: S{}E{}", entry.season, entry.episode + 1))
+                ),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+pub fn export(entries: &[Entry], path: &Path) -> Result<(), String> {
+    fs::write(path, to_string(entries, Local::now().naive_local()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Season, episode and air date of the unwatched episodes that haven't aired before today.
+fn upcoming_episodes(entry: &Entry, now: NaiveDateTime) -> Vec<(u32, u32, NaiveDate)> {
+    let Some(first_air_date) = entry.next_air_date else {
+        return Vec::new();
+    };
+    let count = if entry.max_episodes > 0 {
+        entry.max_episodes.saturating_sub(entry.watched_episodes)
+    } else {
+        1
+    };
+
+    (0..count.min(MAX_UPCOMING_EPISODES))
+        .map(|i| {
+            (
+                entry.season,
+                entry.episode + 1 + i,
+                first_air_date + Duration::weeks(i as i64),
+            )
+        })
+        .filter(|(_, _, date)| *date >= now.date())
+        .collect()
+}
+
+/// Escapes a TEXT value as required by RFC 5545.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Terminates a content line with CRLF, folding it so no line exceeds 75 octets.
+fn fold_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + 2);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
@@ -0,0 +1,87 @@
+use crate::import::{Importer, ParsedImport};
+use crate::{config::Config, Entry};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Versioned JSON document holding the whole watchlist.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchlistDocument {
+    pub schema_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub entries: Vec<ExportedEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedEntry {
+    pub ordering: usize,
+    #[serde(flatten)]
+    pub entry: Entry,
+}
+
+impl WatchlistDocument {
+    pub fn new(entries: &[Entry]) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            exported_at: Utc::now(),
+            entries: entries
+                .iter()
+                .enumerate()
+                .map(|(ordering, entry)| ExportedEntry {
+                    ordering,
+                    entry: entry.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Entries sorted by their exported ordering.
+    pub fn into_entries(mut self) -> Vec<Entry> {
+        self.entries.sort_by_key(|e| e.ordering);
+        self.entries.into_iter().map(|e| e.entry).collect()
+    }
+}
+
+pub fn to_string(entries: &[Entry]) -> Result<String, String> {
+    serde_json::to_string_pretty(&WatchlistDocument::new(entries))
+        .map_err(|e| format!("Failed to serialize watchlist: {}", e))
+}
+
+pub fn from_str(data: &str) -> Result<Vec<Entry>, String> {
+    let document: WatchlistDocument =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse JSON export: {}", e))?;
+
+    if document.schema_version > SCHEMA_VERSION {
+        return Err(format!(
+            "Unsupported schema version {} (expected {} or lower)",
+            document.schema_version, SCHEMA_VERSION
+        ));
+    }
+
+    Ok(document.into_entries())
+}
+
+pub fn export(entries: &[Entry], path: &Path) -> Result<(), String> {
+    fs::write(path, to_string(entries)?)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// senarai's own JSON export.
+pub struct JsonImporter;
+
+impl Importer for JsonImporter {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn detect(&self, _path: &Path, data: &str) -> bool {
+        data.trim_start().starts_with('{') && data.contains("\"schema_version\"")
+    }
+
+    fn parse(&self, _path: &Path, data: &str, _config: &Config) -> Result<ParsedImport, String> {
+        from_str(data).map(ParsedImport::from)
+    }
+}
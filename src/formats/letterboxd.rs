@@ -0,0 +1,88 @@
+use crate::import::{Importer, ParsedImport};
+use crate::{config::Config, Entry, Status};
+use std::collections::HashSet;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Letterboxd `watched.csv`, `diary.csv`, `ratings.csv` and `watchlist.csv`.
+pub struct LetterboxdImporter;
+
+impl Importer for LetterboxdImporter {
+    fn name(&self) -> &'static str {
+        "letterboxd"
+    }
+
+    fn detect(&self, _path: &Path, data: &str) -> bool {
+        data.lines()
+            .next()
+            .is_some_and(|header| header.contains("Letterboxd URI"))
+    }
+
+    fn parse(&self, path: &Path, data: &str, _config: &Config) -> Result<ParsedImport, String> {
+        let is_watchlist = path
+            .file_stem()
+            .is_some_and(|stem| stem.to_string_lossy().to_lowercase().contains("watchlist"));
+        from_str(data, is_watchlist)
+    }
+}
+
+pub fn from_str(data: &str, is_watchlist: bool) -> Result<ParsedImport, String> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(data.as_bytes());
+    let name_col = reader
+        .headers()
+        .map_err(|e| format!("Failed to read Letterboxd header: {}", e))?
+        .iter()
+        .position(|h| h == "Name")
+        .ok_or("Letterboxd export has no Name column")?;
+
+    let mut parsed = ParsedImport {
+        match_titles: true,
+        ..Default::default()
+    };
+    // The diary lists rewatches as separate rows.
+    let mut seen = HashSet::new();
+
+    for (i, record) in reader.records().enumerate() {
+        let line = i + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                parsed
+                    .rejected
+                    .push((format!("row {}", line), e.to_string()));
+                continue;
+            }
+        };
+        let title = record.get(name_col).unwrap_or("");
+        if title.is_empty() {
+            parsed
+                .rejected
+                .push((format!("row {}", line), "missing title".to_string()));
+            continue;
+        }
+        if !seen.insert(title.to_string()) {
+            continue;
+        }
+
+        let watched = if is_watchlist { 0 } else { 1 };
+        parsed.entries.push(Entry {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            season: 1,
+            episode: watched,
+            status: if is_watchlist {
+                Status::Planning
+            } else {
+                Status::Completed
+            },
+            watched_episodes: watched,
+            max_episodes: 1,
+            ..Default::default()
+        });
+    }
+
+    Ok(parsed)
+}
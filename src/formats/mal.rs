@@ -0,0 +1,159 @@
+use crate::formats::{has_extension, parse_status};
+use crate::import::{ImportOptions, Importer, ParsedImport};
+use crate::{config::Config, Entry, Status};
+use quick_xml::escape::escape;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// `<myanimelist>` export as produced by MyAnimeList and compatible list sites.
+#[derive(Deserialize)]
+struct MalDocument {
+    #[serde(rename = "anime", default)]
+    anime: Vec<MalAnime>,
+}
+
+/// The subset of an `<anime>` element senarai can represent. Entries have no score or
+/// start and finish dates, so `my_score`, `my_start_date` and `my_finish_date` are neither
+/// read nor written. Numbers are kept as text so a malformed value rejects only its own entry.
+#[derive(Deserialize)]
+struct MalAnime {
+    #[serde(default)]
+    series_title: String,
+    #[serde(default)]
+    series_episodes: String,
+    #[serde(default)]
+    my_watched_episodes: String,
+    #[serde(default)]
+    my_status: String,
+}
+
+fn mal_status(status: Status) -> &'static str {
+    match status {
+        Status::Planning => "Plan to Watch",
+        Status::Watching => "Watching",
+        Status::Completed => "Completed",
+        Status::Dropped => "Dropped",
+    }
+}
+
+pub fn to_string(entries: &[Entry]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n<myanimelist>\n");
+    xml.push_str("\t<myinfo>\n\t\t<user_export_type>1</user_export_type>\n");
+    xml.push_str(&format!(
+        "\t\t<user_total_anime>{}</user_total_anime>\n\t</myinfo>\n",
+        entries.len()
+    ));
+
+    for entry in entries {
+        xml.push_str("\t<anime>\n");
+        xml.push_str("\t\t<series_animedb_id>0</series_animedb_id>\n");
+        xml.push_str(&format!(
+            "\t\t<series_title>{}</series_title>\n",
+            escape(entry.title.as_str())
+        ));
+        xml.push_str(&format!(
+            "\t\t<series_episodes>{}</series_episodes>\n",
+            entry.max_episodes
+        ));
+        xml.push_str(&format!(
+            "\t\t<my_watched_episodes>{}</my_watched_episodes>\n",
+            entry.watched_episodes
+        ));
+        xml.push_str(&format!(
+            "\t\t<my_status>{}</my_status>\n",
+            mal_status(entry.status)
+        ));
+        xml.push_str("\t\t<update_on_import>1</update_on_import>\n");
+        xml.push_str("\t</anime>\n");
+    }
+
+    xml.push_str("</myanimelist>\n");
+    xml
+}
+
+pub fn from_str(data: &str, options: &ImportOptions) -> Result<ParsedImport, String> {
+    let document: MalDocument = quick_xml::de::from_str(data)
+        .map_err(|e| format!("Failed to parse MyAnimeList export: {}", e))?;
+
+    // MAL entries carry no senarai id, so merging updates entries with the same title.
+    let mut parsed = ParsedImport {
+        match_titles: true,
+        ..Default::default()
+    };
+    for (i, anime) in document.anime.into_iter().enumerate() {
+        let label = if anime.series_title.trim().is_empty() {
+            format!("anime {}", i + 1)
+        } else {
+            anime.series_title.trim().to_string()
+        };
+        match parse_anime(anime, options) {
+            Ok(entry) => parsed.entries.push(entry),
+            Err(reason) => parsed.rejected.push((label, reason)),
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_anime(anime: MalAnime, options: &ImportOptions) -> Result<Entry, String> {
+    let number = |name: &str, value: &str| match value.trim() {
+        "" => Ok(0),
+        value => value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid {} '{}'", name, value)),
+    };
+
+    let title = anime.series_title.trim();
+    if title.is_empty() {
+        return Err("missing title".to_string());
+    }
+
+    let status = match anime.my_status.trim() {
+        "" => Status::Planning,
+        // Older exports use numeric statuses.
+        "1" => Status::Watching,
+        "2" => Status::Completed,
+        "3" => options.on_hold_status,
+        "4" => Status::Dropped,
+        "6" => Status::Planning,
+        value => {
+            parse_status(value, options).ok_or_else(|| format!("unknown status '{}'", value))?
+        }
+    };
+
+    let watched_episodes = number("watched episodes", &anime.my_watched_episodes)?;
+
+    Ok(Entry {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        season: 1,
+        episode: watched_episodes,
+        status,
+        watched_episodes,
+        max_episodes: number("episode count", &anime.series_episodes)?,
+        ..Default::default()
+    })
+}
+
+pub fn export(entries: &[Entry], path: &Path) -> Result<(), String> {
+    fs::write(path, to_string(entries))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// MyAnimeList XML export.
+pub struct MalImporter;
+
+impl Importer for MalImporter {
+    fn name(&self) -> &'static str {
+        "mal"
+    }
+
+    fn detect(&self, path: &Path, data: &str) -> bool {
+        has_extension(path, "xml") || data.contains("<myanimelist>")
+    }
+
+    fn parse(&self, _path: &Path, data: &str, config: &Config) -> Result<ParsedImport, String> {
+        from_str(data, &config.import)
+    }
+}
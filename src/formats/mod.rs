@@ -0,0 +1,151 @@
+use crate::config::Config;
+use crate::import::{ImportOptions, Importer, ParsedImport};
+use crate::{Entry, Status};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+pub mod csv;
+pub mod ics;
+pub mod json;
+pub mod letterboxd;
+pub mod mal;
+pub mod report;
+pub mod trakt;
+pub mod tvtime;
+
+/// Every supported import format, in detection order: specific formats before generic ones.
+pub fn importers() -> Vec<Box<dyn Importer>> {
+    vec![
+        Box::new(json::JsonImporter),
+        Box::new(trakt::TraktImporter),
+        Box::new(mal::MalImporter),
+        Box::new(letterboxd::LetterboxdImporter),
+        Box::new(tvtime::TvTimeImporter),
+        Box::new(csv::CsvImporter),
+    ]
+}
+
+pub(crate) fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+/// Writes `entries` to `path`, choosing the format from the file extension (JSON by default).
+pub fn export_file(entries: &[Entry], path: &Path) -> Result<(), String> {
+    if has_extension(path, "csv") {
+        csv::export(entries, path)
+    } else if has_extension(path, "xml") {
+        mal::export(entries, path)
+    } else if has_extension(path, "ics") {
+        ics::export(entries, path)
+    } else if has_extension(path, "md") {
+        report::export(entries, path, report::ReportFormat::Markdown, None)
+    } else if has_extension(path, "html") || has_extension(path, "htm") {
+        report::export(entries, path, report::ReportFormat::Html, None)
+    } else {
+        json::export(entries, path)
+    }
+}
+
+/// Reads an import file with the named importer, or the first one that recognizes it.
+pub fn import_file(
+    path: &Path,
+    format: Option<&str>,
+    config: &Config,
+) -> Result<ParsedImport, String> {
+    let data = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let importers = importers();
+
+    let importer = match format {
+        Some(name) => importers.iter().find(|i| i.name() == name).ok_or_else(|| {
+            let names: Vec<_> = importers.iter().map(|i| i.name()).collect();
+            format!(
+                "Unknown import format '{}' (available: {})",
+                name,
+                names.join(", ")
+            )
+        })?,
+        None => importers
+            .iter()
+            .find(|i| i.detect(path, &data))
+            .ok_or_else(|| format!("Unrecognized import file {}", path.display()))?,
+    };
+
+    importer.parse(path, &data, config)
+}
+
+/// Collects watched episodes per show, keeping shows in the order they were first seen.
+#[derive(Default)]
+pub(crate) struct EpisodeTally {
+    shows: Vec<(String, BTreeSet<(u32, u32)>)>,
+    index: HashMap<String, usize>,
+}
+
+impl EpisodeTally {
+    pub(crate) fn add(&mut self, title: &str, season: u32, episode: u32) {
+        let i = *self.index.entry(title.to_string()).or_insert_with(|| {
+            self.shows.push((title.to_string(), BTreeSet::new()));
+            self.shows.len() - 1
+        });
+        self.shows[i].1.insert((season, episode));
+    }
+
+    /// One entry per show, positioned at its latest watched episode.
+    pub(crate) fn into_entries(self, status: Status) -> Vec<Entry> {
+        self.shows
+            .into_iter()
+            .map(|(title, episodes)| {
+                let (season, episode) = episodes.last().copied().unwrap_or((1, 0));
+                Entry {
+                    id: Uuid::new_v4(),
+                    title,
+                    season: season.max(1),
+                    episode,
+                    status,
+                    watched_episodes: episodes.len() as u32,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parses a status name leniently: case-insensitive, ignoring `-`/`_`, with common synonyms.
+///
+/// The configured synonyms are checked first (keys compared the same way), and "on hold" style
+/// values map to the configured status since senarai has no dedicated status for them.
+pub fn parse_status(value: &str, options: &ImportOptions) -> Option<Status> {
+    let normalized = normalize_status(value);
+
+    if let Some(status) = options
+        .status_synonyms
+        .iter()
+        .find(|(name, _)| normalize_status(name) == normalized)
+        .map(|(_, status)| *status)
+    {
+        return Some(status);
+    }
+
+    match normalized.as_str() {
+        "planning" | "plan to watch" | "plantowatch" | "ptw" | "planned" | "to watch"
+        | "want to watch" | "watchlist" => Some(Status::Planning),
+        "watching" | "currently watching" | "in progress" | "started" => Some(Status::Watching),
+        "completed" | "complete" | "finished" | "watched" | "done" => Some(Status::Completed),
+        "dropped" | "abandoned" | "stopped" => Some(Status::Dropped),
+        "on hold" | "onhold" | "paused" | "hold" => Some(options.on_hold_status),
+        _ => None,
+    }
+}
+
+fn normalize_status(value: &str) -> String {
+    value
+        .trim()
+        .to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,235 @@
+use crate::{Entry, Status};
+use chrono::Local;
+use std::fs;
+use std::path::Path;
+
+/// Layout used when no template file is given. Placeholders are listed in [`render`].
+pub const MARKDOWN_TEMPLATE: &str = "# Watchlist
+
+{{summary}}
+
+{{board}}
+{{dropped}}_Generated by senarai on {{generated}}._
+";
+
+pub const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Watchlist</title>
+<style>
+body { font-family: system-ui, sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: .25rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1rem; }
+th, td { text-align: left; padding: .35rem .5rem; border-bottom: 1px solid #eee; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+progress { width: 8rem; vertical-align: middle; }
+.dropped { color: #888; }
+footer { color: #888; font-size: .85rem; }
+</style>
+</head>
+<body>
+<h1>Watchlist</h1>
+<p>{{summary}}</p>
+{{board}}
+{{dropped}}
+<footer>Generated by senarai on {{generated}}.</footer>
+</body>
+</html>
+"#;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+/// Renders the board grouped by status into `template`, or the built-in layout.
+///
+/// Templates may use `{{summary}}`, `{{generated}}`, `{{board}}` (the Planning, Watching and
+/// Completed sections), `{{planning}}`, `{{watching}}`, `{{completed}}` and `{{dropped}}`. The
+/// dropped section is left empty when nothing was dropped.
+pub fn render(entries: &[Entry], format: ReportFormat, template: Option<&str>) -> String {
+    let template = template.unwrap_or(match format {
+        ReportFormat::Markdown => MARKDOWN_TEMPLATE,
+        ReportFormat::Html => HTML_TEMPLATE,
+    });
+
+    let section = |status: Status| {
+        let group: Vec<&Entry> = entries.iter().filter(|e| e.status == status).collect();
+        match format {
+            ReportFormat::Markdown => markdown_section(status, &group),
+            ReportFormat::Html => html_section(status, &group),
+        }
+    };
+    let dropped = if entries.iter().any(|e| e.status == Status::Dropped) {
+        section(Status::Dropped) + "\n"
+    } else {
+        String::new()
+    };
+    let board: Vec<String> = Status::BOARD
+        .iter()
+        .map(|&status| section(status))
+        .collect();
+
+    let summary = summary(entries);
+    let summary = match format {
+        ReportFormat::Markdown => summary,
+        ReportFormat::Html => escape_html(&summary),
+    };
+
+    fill(template, |name| match name {
+        "summary" => Some(summary.clone()),
+        "generated" => Some(Local::now().format("%Y-%m-%d %H:%M").to_string()),
+        "board" => Some(board.join("\n")),
+        "planning" => Some(board[0].clone()),
+        "watching" => Some(board[1].clone()),
+        "completed" => Some(board[2].clone()),
+        "dropped" => Some(dropped.clone()),
+        _ => None,
+    })
+}
+
+/// Replaces each `{{name}}` in `template` with `value(name)` in a single pass, so inserted
+/// text is never searched for placeholders. Unknown names are kept as written.
+fn fill(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after
+            .find("}}")
+            .and_then(|end| Some((end, value(&after[..end])?)))
+        {
+            Some((end, text)) => {
+                out.push_str(&text);
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Writes a report to `path`, reading the layout from `template` if given.
+pub fn export(
+    entries: &[Entry],
+    path: &Path,
+    format: ReportFormat,
+    template: Option<&Path>,
+) -> Result<(), String> {
+    let data = render(entries, format, read_template(template)?.as_deref());
+    fs::write(path, data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn read_template(path: Option<&Path>) -> Result<Option<String>, String> {
+    path.map(|path| {
+        fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read template {}: {}", path.display(), e))
+    })
+    .transpose()
+}
+
+fn summary(entries: &[Entry]) -> String {
+    let counts: Vec<String> = Status::BOARD
+        .iter()
+        .chain(std::iter::once(&Status::Dropped))
+        .map(|&status| {
+            let count = entries.iter().filter(|e| e.status == status).count();
+            format!("{} {}", count, status.to_string().to_lowercase())
+        })
+        .collect();
+    format!("{} entries: {}", entries.len(), counts.join(", "))
+}
+
+/// Watched out of total episodes with the percentage, or `-` while the total is unknown.
+fn progress_text(entry: &Entry) -> String {
+    if entry.max_episodes == 0 && entry.status != Status::Completed {
+        return "-".to_string();
+    }
+    if entry.max_episodes == 0 {
+        return format!("{}%", entry.progress());
+    }
+    format!(
+        "{}/{} ({}%)",
+        entry.watched_episodes,
+        entry.max_episodes,
+        entry.progress()
+    )
+}
+
+fn markdown_section(status: Status, entries: &[&Entry]) -> String {
+    let mut out = format!("## {} ({})\n\n", status, entries.len());
+    if entries.is_empty() {
+        out.push_str("_No entries._\n");
+        return out;
+    }
+
+    out.push_str("| Title | Season | Episode | Progress |\n");
+    out.push_str("| :---- | -----: | ------: | -------: |\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            escape_markdown(&entry.title),
+            entry.season,
+            entry.episode,
+            progress_text(entry)
+        ));
+    }
+    out
+}
+
+fn html_section(status: Status, entries: &[&Entry]) -> String {
+    let class = status.to_string().to_lowercase();
+    let mut out = format!(
+        "<section class=\"{}\">\n<h2>{} ({})</h2>\n",
+        class,
+        status,
+        entries.len()
+    );
+    if entries.is_empty() {
+        out.push_str("<p><em>No entries.</em></p>\n</section>\n");
+        return out;
+    }
+
+    out.push_str(
+        "<table>\n<thead><tr><th>Title</th><th>Season</th><th>Episode</th><th>Progress</th></tr></thead>\n<tbody>\n",
+    );
+    for entry in entries {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td><progress max=\"100\" value=\"{}\"></progress> {}</td></tr>\n",
+            escape_html(&entry.title),
+            entry.season,
+            entry.episode,
+            entry.progress(),
+            progress_text(entry)
+        ));
+    }
+    out.push_str("</tbody>\n</table>\n</section>\n");
+    out
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,128 @@
+use crate::formats::EpisodeTally;
+use crate::import::{Importer, ParsedImport};
+use crate::{config::Config, Entry, Status};
+use serde::Deserialize;
+use std::path::Path;
+use uuid::Uuid;
+
+/// One element of a Trakt export array. Watched lists, the watchlist and history share this
+/// shape with different fields filled in.
+#[derive(Deserialize)]
+struct TraktItem {
+    #[serde(default)]
+    show: Option<TraktMedia>,
+    #[serde(default)]
+    movie: Option<TraktMedia>,
+    #[serde(default)]
+    seasons: Vec<TraktSeason>,
+    #[serde(default)]
+    episode: Option<TraktHistoryEpisode>,
+    #[serde(default)]
+    listed_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TraktMedia {
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TraktSeason {
+    number: u32,
+    #[serde(default)]
+    episodes: Vec<TraktEpisode>,
+}
+
+#[derive(Deserialize)]
+struct TraktEpisode {
+    number: u32,
+}
+
+#[derive(Deserialize)]
+struct TraktHistoryEpisode {
+    season: u32,
+    number: u32,
+}
+
+/// Trakt `watched-shows.json`, `watched-movies.json`, `watchlist*.json` and `history*.json`.
+pub struct TraktImporter;
+
+impl Importer for TraktImporter {
+    fn name(&self) -> &'static str {
+        "trakt"
+    }
+
+    fn detect(&self, _path: &Path, data: &str) -> bool {
+        data.trim_start().starts_with('[')
+            && (data.contains("\"show\"") || data.contains("\"movie\""))
+    }
+
+    fn parse(&self, _path: &Path, data: &str, _config: &Config) -> Result<ParsedImport, String> {
+        from_str(data)
+    }
+}
+
+pub fn from_str(data: &str) -> Result<ParsedImport, String> {
+    let items: Vec<TraktItem> =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse Trakt export: {}", e))?;
+
+    let mut parsed = ParsedImport {
+        match_titles: true,
+        ..Default::default()
+    };
+    let mut tally = EpisodeTally::default();
+
+    for (i, item) in items.into_iter().enumerate() {
+        let (media, is_movie) = match (item.show, item.movie) {
+            (Some(show), _) => (show, false),
+            (None, Some(movie)) => (movie, true),
+            (None, None) => {
+                parsed.rejected.push((
+                    format!("item {}", i + 1),
+                    "neither show nor movie".to_string(),
+                ));
+                continue;
+            }
+        };
+        let Some(title) = media.title.filter(|t| !t.trim().is_empty()) else {
+            parsed
+                .rejected
+                .push((format!("item {}", i + 1), "missing title".to_string()));
+            continue;
+        };
+
+        if item.listed_at.is_some() {
+            parsed.entries.push(Entry {
+                id: Uuid::new_v4(),
+                title,
+                season: 1,
+                status: Status::Planning,
+                max_episodes: if is_movie { 1 } else { 0 },
+                ..Default::default()
+            });
+        } else if is_movie {
+            parsed.entries.push(Entry {
+                id: Uuid::new_v4(),
+                title,
+                season: 1,
+                episode: 1,
+                status: Status::Completed,
+                watched_episodes: 1,
+                max_episodes: 1,
+                ..Default::default()
+            });
+        } else if let Some(episode) = item.episode {
+            tally.add(&title, episode.season, episode.number);
+        } else {
+            for season in item.seasons.iter().filter(|s| s.number > 0) {
+                for episode in &season.episodes {
+                    tally.add(&title, season.number, episode.number);
+                }
+            }
+        }
+    }
+
+    parsed.entries.extend(tally.into_entries(Status::Watching));
+    Ok(parsed)
+}
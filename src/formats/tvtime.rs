@@ -0,0 +1,97 @@
+use crate::formats::EpisodeTally;
+use crate::import::{Importer, ParsedImport};
+use crate::{config::Config, Entry, Status};
+use std::collections::HashSet;
+use std::path::Path;
+use uuid::Uuid;
+
+/// TV Time data export: `seen_episode.csv` for progress, `followed_tv_show.csv` for shows
+/// that were followed but not started.
+pub struct TvTimeImporter;
+
+impl Importer for TvTimeImporter {
+    fn name(&self) -> &'static str {
+        "tvtime"
+    }
+
+    fn detect(&self, _path: &Path, data: &str) -> bool {
+        data.lines()
+            .next()
+            .is_some_and(|header| header.contains("tv_show_name"))
+    }
+
+    fn parse(&self, _path: &Path, data: &str, _config: &Config) -> Result<ParsedImport, String> {
+        from_str(data)
+    }
+}
+
+pub fn from_str(data: &str) -> Result<ParsedImport, String> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(::csv::Trim::All)
+        .from_reader(data.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Failed to read TV Time header: {}", e))?
+        .clone();
+    let column = |name: &str| headers.iter().position(|h| h == name);
+
+    let title_col = column("tv_show_name").ok_or("TV Time export has no tv_show_name column")?;
+    let season_col = column("episode_season_number");
+    let episode_col = column("episode_number");
+
+    let mut parsed = ParsedImport {
+        match_titles: true,
+        ..Default::default()
+    };
+    let mut tally = EpisodeTally::default();
+    let mut followed = HashSet::new();
+
+    for (i, record) in reader.records().enumerate() {
+        let line = i + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                parsed
+                    .rejected
+                    .push((format!("row {}", line), e.to_string()));
+                continue;
+            }
+        };
+        let title = record.get(title_col).unwrap_or("");
+        if title.is_empty() {
+            parsed
+                .rejected
+                .push((format!("row {}", line), "missing title".to_string()));
+            continue;
+        }
+
+        match (season_col, episode_col) {
+            (Some(season_col), Some(episode_col)) => {
+                let season = record.get(season_col).unwrap_or("");
+                let episode = record.get(episode_col).unwrap_or("");
+                match (season.parse::<u32>(), episode.parse::<u32>()) {
+                    (Ok(season), Ok(episode)) => tally.add(title, season, episode),
+                    _ => parsed.rejected.push((
+                        format!("row {} '{}'", line, title),
+                        format!("invalid episode S{} E{}", season, episode),
+                    )),
+                }
+            }
+            _ => {
+                if followed.insert(title.to_string()) {
+                    parsed.entries.push(Entry {
+                        id: Uuid::new_v4(),
+                        title: title.to_string(),
+                        season: 1,
+                        status: Status::Planning,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    parsed.entries.extend(tally.into_entries(Status::Watching));
+    Ok(parsed)
+}
@@ -0,0 +1,49 @@
+/// Matches `query` against `text`, ignoring case. Each word of the query has to appear in
+/// `text` in order, but not necessarily next to each other, so `brba` finds "Breaking Bad".
+/// Returns the positions (in chars) of the matched characters, or `None` if a word is
+/// missing. An empty query matches everything.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<Vec<usize>> {
+    let text: Vec<char> = text.chars().map(lowercase).collect();
+    let mut positions = Vec::new();
+    for word in query.split_whitespace() {
+        let word: Vec<char> = word.chars().map(lowercase).collect();
+        positions.extend(match_word(&word, &text)?);
+    }
+    positions.sort_unstable();
+    positions.dedup();
+    Some(positions)
+}
+
+/// Splits a filter into the words matched against titles and the `tag:` terms, which
+/// match entries with a tag starting with the given text.
+pub fn split_tag_terms(query: &str) -> (String, Vec<String>) {
+    let mut words = Vec::new();
+    let mut tags = Vec::new();
+    for word in query.split_whitespace() {
+        match word.strip_prefix("tag:") {
+            Some(tag) if !tag.is_empty() => tags.push(tag.to_lowercase()),
+            Some(_) => {}
+            None => words.push(word),
+        }
+    }
+    (words.join(" "), tags)
+}
+
+/// Prefers the word as one piece, so "off" highlights "Office" rather than "O...f...f".
+fn match_word(word: &[char], text: &[char]) -> Option<Vec<usize>> {
+    if let Some(start) = text.windows(word.len()).position(|window| window == word) {
+        return Some((start..start + word.len()).collect());
+    }
+    let mut positions = Vec::with_capacity(word.len());
+    let mut chars = text.iter().enumerate();
+    for c in word {
+        let (pos, _) = chars.find(|(_, t)| *t == c)?;
+        positions.push(pos);
+    }
+    Some(positions)
+}
+
+/// Lowercases one char to one char, so positions in the lowercased text match the original.
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
@@ -0,0 +1,242 @@
+use crate::{config::Config, Entry, Status};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImportMode {
+    /// Discard the current watchlist and use the imported entries instead.
+    Replace,
+    /// Update entries with a matching id and append the rest.
+    Merge,
+}
+
+/// How importers interpret status names, from the `import` section of `config.yaml`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ImportOptions {
+    /// Extra status names, e.g. `"backlog": Planning`.
+    pub status_synonyms: HashMap<String, Status>,
+    /// Status used for "on hold" / "paused" entries.
+    pub on_hold_status: Status,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub skipped: Vec<(String, String)>,
+    pub unchanged: usize,
+}
+
+impl ImportReport {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.updated.is_empty() || !self.removed.is_empty()
+    }
+
+    /// One line per changed or skipped entry, prefixed with `+`, `~`, `-` or `!`.
+    pub fn details(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.extend(self.added.iter().map(|t| format!("+ {}", t)));
+        lines.extend(self.updated.iter().map(|t| format!("~ {}", t)));
+        lines.extend(self.removed.iter().map(|t| format!("- {}", t)));
+        lines.extend(
+            self.skipped
+                .iter()
+                .map(|(t, reason)| format!("! {} ({})", t, reason)),
+        );
+        lines
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} updated, {} removed, {} skipped, {} unchanged",
+            self.added.len(),
+            self.updated.len(),
+            self.removed.len(),
+            self.skipped.len(),
+            self.unchanged
+        )
+    }
+}
+
+/// A file format entries can be imported from. New formats only need to be added to
+/// `formats::importers`.
+pub trait Importer {
+    /// Name used with `senarai import --format`.
+    fn name(&self) -> &'static str;
+    /// Whether the file at `path` with contents `data` looks like this format.
+    fn detect(&self, path: &Path, data: &str) -> bool;
+    fn parse(&self, path: &Path, data: &str, config: &Config) -> Result<ParsedImport, String>;
+}
+
+/// Entries read from an import file, plus the records that could not be read.
+#[derive(Clone, Debug, Default)]
+pub struct ParsedImport {
+    pub entries: Vec<Entry>,
+    /// `(record, reason)` pairs, e.g. `("row 4", "unknown status 'x'")`.
+    pub rejected: Vec<(String, String)>,
+    /// Whether merging should update existing entries with the same title. Set by importers
+    /// for other trackers, whose entries never carry a senarai id.
+    pub match_titles: bool,
+}
+
+impl From<Vec<Entry>> for ParsedImport {
+    fn from(entries: Vec<Entry>) -> Self {
+        Self {
+            entries,
+            ..Default::default()
+        }
+    }
+}
+
+/// The watchlist an import would produce, together with what changes.
+#[derive(Debug)]
+pub struct ImportPlan {
+    pub entries: Vec<Entry>,
+    pub report: ImportReport,
+}
+
+/// A loaded import with both of its plans, built once while the preview is shown.
+#[derive(Debug)]
+pub struct ImportPreview {
+    /// Entries read from the file.
+    pub incoming: usize,
+    pub merge: ImportPlan,
+    pub replace: ImportPlan,
+}
+
+impl ImportPreview {
+    pub fn new(existing: &[Entry], incoming: ParsedImport) -> Self {
+        Self {
+            incoming: incoming.entries.len(),
+            merge: plan(existing, incoming.clone(), ImportMode::Merge),
+            replace: plan(existing, incoming, ImportMode::Replace),
+        }
+    }
+
+    pub fn into_plan(self, mode: ImportMode) -> ImportPlan {
+        match mode {
+            ImportMode::Merge => self.merge,
+            ImportMode::Replace => self.replace,
+        }
+    }
+}
+
+/// Whether an entry with the same title already exists, matching the check in `App::add_entry`.
+pub fn title_exists(entries: &[Entry], title: &str) -> bool {
+    entries.iter().any(|e| e.title == title)
+}
+
+pub fn plan(existing: &[Entry], incoming: ParsedImport, mode: ImportMode) -> ImportPlan {
+    let mut plan = match mode {
+        ImportMode::Replace => plan_replace(existing, incoming.entries),
+        ImportMode::Merge => plan_merge(existing, incoming.entries, incoming.match_titles),
+    };
+    plan.report.skipped.extend(incoming.rejected);
+    plan
+}
+
+fn plan_replace(existing: &[Entry], incoming: Vec<Entry>) -> ImportPlan {
+    let mut report = ImportReport::default();
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for mut entry in incoming {
+        if !seen_ids.insert(entry.id) {
+            report
+                .skipped
+                .push((entry.title, "duplicate id".to_string()));
+            continue;
+        }
+        if title_exists(&entries, &entry.title) {
+            report
+                .skipped
+                .push((entry.title, "duplicate title".to_string()));
+            continue;
+        }
+
+        match existing.iter().find(|e| e.id == entry.id) {
+            Some(current) if current.same_content(&entry) => {
+                entry.updated_at = current.updated_at;
+                report.unchanged += 1;
+            }
+            Some(_) => report.updated.push(entry.title.clone()),
+            None => report.added.push(entry.title.clone()),
+        }
+        entries.push(entry);
+    }
+
+    report.removed = existing
+        .iter()
+        .filter(|e| !seen_ids.contains(&e.id))
+        .map(|e| e.title.clone())
+        .collect();
+
+    ImportPlan { entries, report }
+}
+
+fn plan_merge(existing: &[Entry], incoming: Vec<Entry>, match_titles: bool) -> ImportPlan {
+    let mut report = ImportReport::default();
+    let mut entries = existing.to_vec();
+    let mut seen_ids = HashSet::new();
+
+    for entry in incoming {
+        if !seen_ids.insert(entry.id) {
+            report
+                .skipped
+                .push((entry.title, "duplicate id".to_string()));
+            continue;
+        }
+
+        if let Some(current) = entries.iter_mut().find(|e| e.id == entry.id) {
+            if current.same_content(&entry) {
+                report.unchanged += 1;
+            } else {
+                report.updated.push(entry.title.clone());
+                *current = entry;
+            }
+        } else if let Some(current) = entries
+            .iter_mut()
+            .find(|e| match_titles && e.title == entry.title)
+        {
+            if merge_progress(current, &entry) {
+                report.updated.push(entry.title);
+            } else {
+                report.unchanged += 1;
+            }
+        } else if title_exists(&entries, &entry.title) {
+            report
+                .skipped
+                .push((entry.title, "duplicate title".to_string()));
+        } else {
+            report.added.push(entry.title.clone());
+            entries.push(entry);
+        }
+    }
+
+    ImportPlan { entries, report }
+}
+
+/// Copies the tracking fields of `incoming` onto `current`, keeping its id and runtimes.
+fn merge_progress(current: &mut Entry, incoming: &Entry) -> bool {
+    let merged = Entry {
+        status: incoming.status,
+        season: incoming.season,
+        episode: incoming.episode,
+        watched_episodes: incoming.watched_episodes,
+        max_episodes: incoming.max_episodes.max(current.max_episodes),
+        ..current.clone()
+    };
+    if merged == *current {
+        return false;
+    }
+    *current = merged;
+    true
+}
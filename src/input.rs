@@ -1,8 +1,9 @@
-use crate::{app::App, app::InputMode, Status};
+use crate::action::Action;
+use crate::keymap::{KeyAction, KeyChord, KeyContext, KeyMatch};
+use crate::{app::App, app::InputMode, consts, import::ImportMode, stats};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
-use unicode_segmentation::UnicodeSegmentation;
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum InputResult {
     Quit,
     Error(String),
@@ -13,14 +14,15 @@ pub enum InputResult {
 pub fn handle_input(app: &mut App) -> InputResult {
     match event::poll(std::time::Duration::from_millis(50)) {
         Ok(true) => match event::read() {
-            Ok(Event::Key(key)) => {
-                if key.kind == event::KeyEventKind::Press {
-                    return handle_key(key, app);
-                }
+            Ok(Event::Key(key)) if key.kind == event::KeyEventKind::Press => {
+                return handle_key(key, app);
             }
             Ok(Event::Mouse(mouse)) => {
                 return handle_mouse(mouse, app);
             }
+            Ok(Event::Paste(text)) => {
+                return handle_paste(text, app);
+            }
             Err(e) => return InputResult::Error(e.to_string()),
             _ => {}
         },
@@ -30,504 +32,421 @@ pub fn handle_input(app: &mut App) -> InputResult {
     InputResult::Success
 }
 
-fn handle_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match app.input_mode {
-        InputMode::Normal => handle_normal_mode_key(key, app),
-        InputMode::Adding | InputMode::Editing => handle_input_mode_key(key, app),
-        InputMode::MaxEpisodes => handle_max_episodes_input_mode_key(key, app),
-        InputMode::ConfirmDelete => handle_confirm_delete_mode_key(key, app),
-        InputMode::ConfirmDeleteAllDropped => handle_confirm_delete_mode_key(key, app),
-        InputMode::Dropped => handle_dropped_mode_key(key, app),
-        InputMode::TotalEpisodes => handle_total_episodes_mode_key(key, app),
-    }
+/// Handles one key press the way the TUI does.
+pub fn handle_key(key: KeyEvent, app: &mut App) -> InputResult {
+    let actions = match app.input_mode {
+        InputMode::Normal => normal_mode_actions(key, app),
+        InputMode::Adding | InputMode::Editing | InputMode::ExportPath | InputMode::ImportPath => {
+            text_input_actions(key)
+        }
+        InputMode::Command => command_actions(key),
+        InputMode::Filter => filter_actions(key),
+        InputMode::ConfirmImport => confirm_import_actions(key),
+        InputMode::MaxEpisodes
+        | InputMode::Runtime
+        | InputMode::SeasonRuntime
+        | InputMode::AirDate
+        | InputMode::WatchSession => text_input_actions(key),
+        InputMode::ConfirmDelete => confirm_delete_actions(key),
+        InputMode::ConfirmDeleteAllDropped => confirm_delete_actions(key),
+        InputMode::Dropped => dropped_mode_actions(key, app),
+        InputMode::TotalEpisodes => total_episodes_mode_actions(key, app),
+        InputMode::Activity => activity_actions(key, app),
+    };
+    dispatch_all(app, actions)
 }
 
-fn handle_total_episodes_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match key.code {
-        KeyCode::Char('o') | KeyCode::Enter | KeyCode::Esc => {
-            app.show_total_episodes_popup = false;
-            app.input_mode = InputMode::Normal;
-        }
-        KeyCode::Char('#') => {
-            if app.entry.get(app.selected_index).is_some() {
-                app.input.clear();
-                app.cursor_position = 0;
-                app.input_mode = InputMode::MaxEpisodes;
-            }
-        }
-        KeyCode::Char('+') => {
-            app.increment_watched_episodes();
-            return InputResult::Modified;
-        }
-        KeyCode::Char('-') => {
-            app.decrement_watched_episodes();
-            return InputResult::Modified;
-        }
-        _ => {}
+/// Pasted text goes into the input being typed in one piece, instead of as key presses.
+pub fn handle_paste(text: String, app: &mut App) -> InputResult {
+    if app.input_mode.uses_input_bar() || app.input_mode.is_popup_input() {
+        dispatch_all(app, vec![Action::Paste(text)])
+    } else {
+        InputResult::Success
     }
-    InputResult::Success
 }
 
-fn handle_max_episodes_input_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match key.code {
-        KeyCode::Enter => {
-            let value = app.input.parse::<u32>().unwrap_or(0);
-            app.set_max_episodes(value);
-            app.input.clear();
-            app.cursor_position = 0;
-            app.input_mode = InputMode::TotalEpisodes;
-            return InputResult::Modified;
-        }
-        KeyCode::Char(c) if c.is_ascii_digit() => {
-            let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
-            let byte_pos = if app.cursor_position >= graphemes.len() {
-                app.input.len()
-            } else {
-                graphemes
-                    .iter()
-                    .take(app.cursor_position)
-                    .map(|s| s.len())
-                    .sum()
-            };
-            app.input.insert(byte_pos, c);
-            app.cursor_position = clamp_cursor(app.cursor_position + 1, &app.input);
-        }
-        KeyCode::Backspace => {
-            if app.cursor_position > 0 {
-                let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
-                let byte_pos: usize = graphemes
-                    .iter()
-                    .take(app.cursor_position - 1)
-                    .map(|s| s.len())
-                    .sum();
-                let char_len = graphemes[app.cursor_position - 1].len();
-                app.input.replace_range(byte_pos..byte_pos + char_len, "");
-                app.cursor_position = clamp_cursor(app.cursor_position - 1, &app.input);
-            }
-        }
-        KeyCode::Delete => {
-            let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
-            if app.cursor_position < graphemes.len() {
-                let byte_pos: usize = graphemes
-                    .iter()
-                    .take(app.cursor_position)
-                    .map(|s| s.len())
-                    .sum();
-                let char_len = graphemes[app.cursor_position].len();
-                app.input.replace_range(byte_pos..byte_pos + char_len, "");
-            }
-        }
-        KeyCode::Left => {
-            app.cursor_position = clamp_cursor(app.cursor_position.saturating_sub(1), &app.input);
-        }
-        KeyCode::Right => {
-            app.cursor_position = clamp_cursor(app.cursor_position + 1, &app.input);
-        }
-        KeyCode::Esc => {
-            app.input.clear();
-            app.cursor_position = 0;
-            app.input_mode = InputMode::TotalEpisodes;
+/// Applies `actions` in order, stopping at `Quit`.
+fn dispatch_all(app: &mut App, actions: Vec<Action>) -> InputResult {
+    let mut result = InputResult::Success;
+    for action in actions {
+        match app.dispatch(action) {
+            InputResult::Quit => return InputResult::Quit,
+            InputResult::Modified => result = InputResult::Modified,
+            _ => {}
         }
-        _ => {}
     }
-    InputResult::Success
+    result
 }
 
-fn handle_normal_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match key.code {
-        KeyCode::Char('q') => return InputResult::Quit,
-        KeyCode::Up => {
-            if key.modifiers == KeyModifiers::SHIFT {
-                app.move_entry_up_in_column();
-                return InputResult::Modified;
-            } else {
-                app.prev_entry();
-            }
-        }
-        KeyCode::Down => {
-            if key.modifiers == KeyModifiers::SHIFT {
-                app.move_entry_down_in_column();
-                return InputResult::Modified;
-            } else {
-                app.next_entry();
-            }
-        }
-        KeyCode::Right => {
-            if key.modifiers == KeyModifiers::SHIFT {
-                if let Some(s) = app.entry.get(app.selected_index) {
-                    let new_status = s.status.next();
-                    app.move_to(new_status);
-                    return InputResult::Modified;
-                }
-            } else {
-                app.select_next_column();
-            }
-        }
-        KeyCode::Left => {
-            if key.modifiers == KeyModifiers::SHIFT {
-                if let Some(s) = app.entry.get(app.selected_index) {
-                    let new_status = s.status.prev();
-                    app.move_to(new_status);
-                    return InputResult::Modified;
-                }
-            } else {
-                app.select_prev_column();
-            }
-        }
-        KeyCode::Char('a') => {
-            app.input.clear();
-            app.cursor_position = 0;
-            app.input_mode = InputMode::Adding;
-        }
-        KeyCode::Char('e') => {
-            if let Some(s) = app.entry.get(app.selected_index) {
-                app.input = s.title.clone();
-                app.cursor_position = app.input.graphemes(true).count();
-                app.input_mode = InputMode::Editing;
-            }
-        }
-        KeyCode::Char('h') => {
-            app.show_help = !app.show_help;
-        }
-        KeyCode::Char('t') => {
-            app.show_full_title = !app.show_full_title;
-        }
-        KeyCode::Char('o') => {
-            app.show_total_episodes_popup = true;
-            app.input_mode = InputMode::TotalEpisodes;
-        }
-        KeyCode::Char('d') => {
-            app.show_dropped = true;
-            app.input_mode = InputMode::Dropped;
-            let dropped_entries = app.get_dropped_entries();
-            if !dropped_entries.is_empty() {
-                app.selected_index = dropped_entries[0].0;
+/// Resolves `key` against the keymap, collecting count prefixes and the keys of
+/// multi-key bindings. Returns the action and its count once a binding is complete.
+fn resolve_key(
+    app: &mut App,
+    context: KeyContext,
+    key: KeyEvent,
+) -> Option<(KeyAction, Option<u32>)> {
+    let chord = KeyChord::from(key);
+
+    if app.pending_keys.is_empty() && context != KeyContext::Dropped {
+        if let KeyCode::Char(c @ '0'..='9') = chord.code {
+            let starts_count = c != '0' || app.count.is_some();
+            if starts_count && app.keymap.lookup(context, &[chord]) == KeyMatch::None {
+                let digit = c.to_digit(10).unwrap_or(0);
+                let count = app.count.unwrap_or(0).saturating_mul(10) + digit;
+                app.count = Some(count.min(consts::MAX_COUNT));
+                return None;
             }
         }
-        KeyCode::Char('+') => {
-            app.next_episode();
-            return InputResult::Modified;
-        }
-        KeyCode::Char('-') => {
-            app.prev_episode();
-            return InputResult::Modified;
-        }
-        KeyCode::Char('#') => {
-            app.next_season();
-            return InputResult::Modified;
-        }
-        KeyCode::Char('x') => {
-            app.input_mode = InputMode::ConfirmDelete;
+    }
+
+    app.pending_keys.push(chord);
+    match app.keymap.lookup(context, &app.pending_keys) {
+        KeyMatch::Pending => None,
+        KeyMatch::Action(action) => {
+            app.pending_keys.clear();
+            Some((action, app.count.take()))
+        }
+        KeyMatch::None => {
+            app.pending_keys.clear();
+            app.count = None;
+            None
         }
-        _ => {}
     }
-    InputResult::Success
 }
 
-fn handle_input_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match key.code {
-        KeyCode::Enter => {
-            if let InputMode::Adding = app.input_mode {
-                let new_entry: String = app.input.drain(..).collect();
-                app.add_entry(new_entry);
-            } else if let InputMode::Editing = app.input_mode {
-                if let Some(s) = app.entry.get_mut(app.selected_index) {
-                    let new_title: String = app.input.drain(..).collect();
-                    s.title = new_title.clone();
-                    app.edit_entry_title(new_title);
-                }
-            }
-            app.input_mode = InputMode::Normal;
-            app.cursor_position = 0;
-            return InputResult::Modified;
-        }
-        KeyCode::Char(c) => {
-            let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
+fn total_episodes_mode_actions(key: KeyEvent, app: &mut App) -> Vec<Action> {
+    let Some((action, count)) = resolve_key(app, KeyContext::Episodes, key) else {
+        return Vec::new();
+    };
+    let times = count.unwrap_or(1) as usize;
 
-            let should_capitalize = if c.is_alphabetic() {
-                if app.cursor_position == 0 {
-                    true
-                } else {
-                    graphemes
-                        .get(app.cursor_position - 1)
-                        .map_or(false, |&g| g.chars().all(char::is_whitespace))
-                }
-            } else {
-                false
-            };
+    let action = match action {
+        KeyAction::Episodes | KeyAction::Close => Action::Cancel,
+        KeyAction::SetMaxEpisodes => Action::StartInput(InputMode::MaxEpisodes),
+        KeyAction::SetRuntime => Action::StartInput(InputMode::Runtime),
+        KeyAction::SetSeasonRuntime => Action::StartInput(InputMode::SeasonRuntime),
+        KeyAction::SetAirDate => Action::StartInput(InputMode::AirDate),
+        KeyAction::PlanSession => Action::StartInput(InputMode::WatchSession),
+        KeyAction::ClearSessions => Action::ClearWatchSessions,
+        KeyAction::WatchedUp => Action::IncrementWatched(times as u32),
+        KeyAction::WatchedDown => Action::DecrementWatched(times as u32),
+        _ => return Vec::new(),
+    };
+    vec![action]
+}
 
-            let char_to_insert = if should_capitalize {
-                c.to_uppercase().to_string()
-            } else {
-                c.to_string()
-            };
+fn normal_mode_actions(key: KeyEvent, app: &mut App) -> Vec<Action> {
+    let Some((action, count)) = resolve_key(app, KeyContext::Normal, key) else {
+        return Vec::new();
+    };
+    let times = count.unwrap_or(1) as usize;
+    let half_page = (app.half_page() * times) as isize;
+    let page = (app.page() * times) as isize;
 
-            let byte_pos = if app.cursor_position >= graphemes.len() {
-                app.input.len()
-            } else {
-                graphemes
-                    .iter()
-                    .take(app.cursor_position)
-                    .map(|s| s.len())
-                    .sum()
-            };
-            app.input.insert_str(byte_pos, &char_to_insert);
-            app.cursor_position = clamp_cursor(
-                app.cursor_position + char_to_insert.graphemes(true).count(),
-                &app.input,
-            );
-        }
-        KeyCode::Backspace => {
-            if app.cursor_position > 0 {
-                let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
-                let byte_pos: usize = graphemes
-                    .iter()
-                    .take(app.cursor_position - 1)
-                    .map(|s| s.len())
-                    .sum();
-                let char_len = graphemes[app.cursor_position - 1].len();
-                app.input.replace_range(byte_pos..byte_pos + char_len, "");
-                app.cursor_position = clamp_cursor(app.cursor_position - 1, &app.input);
-            }
-        }
-        KeyCode::Delete => {
-            let graphemes = app.input.graphemes(true).collect::<Vec<&str>>();
-            if app.cursor_position < graphemes.len() {
-                let byte_pos: usize = graphemes
-                    .iter()
-                    .take(app.cursor_position)
-                    .map(|s| s.len())
-                    .sum();
-                let char_len = graphemes[app.cursor_position].len();
-                app.input.replace_range(byte_pos..byte_pos + char_len, "");
-            }
-        }
-        KeyCode::Left => {
-            app.cursor_position = clamp_cursor(app.cursor_position.saturating_sub(1), &app.input);
-        }
-        KeyCode::Right => {
-            app.cursor_position = clamp_cursor(app.cursor_position + 1, &app.input);
-        }
-        KeyCode::Esc => {
-            app.input_mode = InputMode::Normal;
-            app.cursor_position = 0;
-        }
-        _ => {}
+    // Entries hidden by the filter can't be changed from the keyboard.
+    let acts_on_selection = matches!(
+        action,
+        KeyAction::Edit
+            | KeyAction::Episodes
+            | KeyAction::NextEpisode
+            | KeyAction::PrevEpisode
+            | KeyAction::NextSeason
+            | KeyAction::Remove
+            | KeyAction::MoveUp
+            | KeyAction::MoveDown
+            | KeyAction::MoveLeft
+            | KeyAction::MoveRight
+            | KeyAction::ToggleSelect
+            | KeyAction::SelectRange
+    );
+    if acts_on_selection && !app.selection_visible() {
+        return Vec::new();
+    }
+    if let Some(scroll) = help_scroll_action(app, action, times) {
+        return vec![scroll];
+    }
+    // The statistics popup covers the board, so only closing it does anything.
+    if app.show_stats {
+        return match action {
+            KeyAction::Quit => vec![Action::Quit],
+            KeyAction::Stats | KeyAction::ClearSelection => vec![Action::Cancel],
+            _ => Vec::new(),
+        };
     }
-    InputResult::Success
-}
 
-fn handle_confirm_delete_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    match key.code {
-        KeyCode::Char('y') | KeyCode::Char('Y') => {
-            match app.input_mode {
-                InputMode::ConfirmDelete => {
-                    if app.show_dropped {
-                        app.force_remove_entry();
-                    } else {
-                        app.drop_entry();
-                    }
-                }
-                InputMode::ConfirmDeleteAllDropped => {
-                    app.force_remove_all_dropped_entries();
-                }
-                _ => {}
-            }
-            app.input_mode = if app.show_dropped {
-                InputMode::Dropped
-            } else {
-                InputMode::Normal
+    let action = match action {
+        KeyAction::Quit => Action::Quit,
+        KeyAction::Up => return vec![Action::SelectPrev; times],
+        KeyAction::Down => return vec![Action::SelectNext; times],
+        KeyAction::Left => return vec![Action::SelectPrevColumn; times],
+        KeyAction::Right => return vec![Action::SelectNextColumn; times],
+        KeyAction::First => Action::SelectRow(Some(count.unwrap_or(1) as usize - 1)),
+        KeyAction::Last => Action::SelectRow(count.map(|c| c as usize - 1)),
+        KeyAction::HalfPageDown => Action::MoveSelection(half_page),
+        KeyAction::HalfPageUp => Action::MoveSelection(-half_page),
+        KeyAction::PageDown => Action::MoveSelection(page),
+        KeyAction::PageUp => Action::MoveSelection(-page),
+        KeyAction::MoveUp => Action::MoveUp(times as u32),
+        KeyAction::MoveDown => Action::MoveDown(times as u32),
+        KeyAction::MoveLeft | KeyAction::MoveRight => {
+            let Some(entry) = app.entry.get(app.selected_index) else {
+                return Vec::new();
             };
-            InputResult::Modified
-        }
-        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-            app.input_mode = if app.show_dropped {
-                InputMode::Dropped
+            if action == KeyAction::MoveLeft {
+                Action::MoveTo(entry.status.prev())
             } else {
-                InputMode::Normal
-            };
-            InputResult::Success
+                Action::MoveTo(entry.status.next())
+            }
         }
-        _ => InputResult::Success,
-    }
+        KeyAction::Add => Action::StartInput(InputMode::Adding),
+        KeyAction::Edit => Action::StartInput(InputMode::Editing),
+        KeyAction::Help => Action::ToggleHelp,
+        KeyAction::ToggleTitle => Action::ToggleFullTitle,
+        KeyAction::Details => Action::ToggleDetails,
+        KeyAction::Stats => Action::ToggleStats,
+        KeyAction::Activity => Action::OpenActivity,
+        KeyAction::Episodes => Action::OpenEpisodes,
+        KeyAction::Dropped => Action::OpenDropped,
+        KeyAction::NextEpisode => Action::NextEpisode(times as u32),
+        KeyAction::PrevEpisode => Action::PrevEpisode(times as u32),
+        KeyAction::NextSeason => Action::NextSeason(times as u32),
+        KeyAction::Remove => Action::RequestRemove,
+        KeyAction::Export => Action::StartInput(InputMode::ExportPath),
+        KeyAction::Import => Action::StartInput(InputMode::ImportPath),
+        KeyAction::Command => Action::StartInput(InputMode::Command),
+        KeyAction::Filter => Action::StartInput(InputMode::Filter),
+        KeyAction::Sort => Action::SetSort(None),
+        KeyAction::ToggleSelect => return vec![Action::ToggleSelected; times],
+        KeyAction::SelectRange => Action::SelectRange(app.selected_index),
+        KeyAction::ClearSelection => Action::Cancel,
+        _ => return Vec::new(),
+    };
+    vec![action]
 }
 
-fn handle_dropped_mode_key(key: KeyEvent, app: &mut App) -> InputResult {
-    let dropped_entries = app.get_dropped_entries();
+/// The activity calendar uses the normal mode keys: up/down pick the previous/next day,
+/// left/right move a week, and first/last jump to the ends of the year.
+fn activity_actions(key: KeyEvent, app: &mut App) -> Vec<Action> {
+    let Some((action, count)) = resolve_key(app, KeyContext::Normal, key) else {
+        return Vec::new();
+    };
+    let times = count.unwrap_or(1) as i64;
+    let year = stats::ACTIVITY_WEEKS as i64 * 7;
 
-    match key.code {
-        KeyCode::Char('q') => return InputResult::Quit,
-        KeyCode::Char('h') => {
-            app.show_help = !app.show_help;
-            return InputResult::Success;
-        }
-        KeyCode::Esc => {
-            app.show_dropped = false;
-            app.input_mode = InputMode::Normal;
-            app.select_first_entry_in_normal_view();
-            return InputResult::Success;
-        }
-        KeyCode::Char('t') => {
-            app.show_full_title = !app.show_full_title;
-            return InputResult::Success;
-        }
-        _ => {}
-    }
+    let action = match action {
+        KeyAction::Quit => Action::Quit,
+        KeyAction::Activity | KeyAction::ClearSelection => Action::Cancel,
+        KeyAction::Up => Action::MoveActivityDay(-times),
+        KeyAction::Down => Action::MoveActivityDay(times),
+        KeyAction::Left => Action::MoveActivityDay(-7 * times),
+        KeyAction::Right => Action::MoveActivityDay(7 * times),
+        KeyAction::First => Action::MoveActivityDay(-year),
+        KeyAction::Last => Action::MoveActivityDay(year),
+        _ => return Vec::new(),
+    };
+    vec![action]
+}
 
-    if dropped_entries.is_empty() {
-        return InputResult::Success;
-    }
+fn confirm_import_actions(key: KeyEvent) -> Vec<Action> {
+    let action = match key.code {
+        KeyCode::Char('m') | KeyCode::Char('M') => Action::ApplyImport(ImportMode::Merge),
+        KeyCode::Char('r') | KeyCode::Char('R') => Action::ApplyImport(ImportMode::Replace),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Action::Cancel,
+        KeyCode::Up => Action::ScrollImportPreview(-1),
+        KeyCode::Down => Action::ScrollImportPreview(1),
+        _ => return Vec::new(),
+    };
+    vec![action]
+}
 
-    let current_pos = dropped_entries
-        .iter()
-        .position(|(idx, _)| *idx == app.selected_index);
+/// Keys for the input bar and the Episodes Watched prompts, with the usual shell line
+/// editing keys; `App::dispatch` decides which characters each prompt accepts.
+fn text_input_actions(key: KeyEvent) -> Vec<Action> {
+    // AltGr sends both modifiers along with the character it types.
+    let ctrl =
+        key.modifiers.contains(KeyModifiers::CONTROL) && !key.modifiers.contains(KeyModifiers::ALT);
+    let alt =
+        key.modifiers.contains(KeyModifiers::ALT) && !key.modifiers.contains(KeyModifiers::CONTROL);
+    let action = match key.code {
+        KeyCode::Enter => Action::SubmitInput,
+        KeyCode::Char('a') if ctrl => Action::CursorHome,
+        KeyCode::Char('e') if ctrl => Action::CursorEnd,
+        KeyCode::Char('w') if ctrl => Action::DeleteWordBackward,
+        KeyCode::Char('u') if ctrl => Action::KillToStart,
+        KeyCode::Char('k') if ctrl => Action::KillToEnd,
+        KeyCode::Char('y') if ctrl => Action::Yank,
+        KeyCode::Char('b') if alt => Action::CursorWordLeft,
+        KeyCode::Char('f') if alt => Action::CursorWordRight,
+        KeyCode::Char('d') if alt => Action::DeleteWordForward,
+        KeyCode::Char(_) if ctrl || alt => return Vec::new(),
+        KeyCode::Char(c) => Action::InsertChar(c),
+        KeyCode::Backspace if ctrl || alt => Action::DeleteWordBackward,
+        KeyCode::Backspace => Action::DeleteBackward,
+        KeyCode::Delete => Action::DeleteForward,
+        KeyCode::Left if ctrl || alt => Action::CursorWordLeft,
+        KeyCode::Right if ctrl || alt => Action::CursorWordRight,
+        KeyCode::Left => Action::CursorLeft,
+        KeyCode::Right => Action::CursorRight,
+        KeyCode::Home => Action::CursorHome,
+        KeyCode::End => Action::CursorEnd,
+        KeyCode::Esc => Action::Cancel,
+        _ => return Vec::new(),
+    };
+    vec![action]
+}
 
-    match key.code {
-        KeyCode::Char('r') => {
-            app.reactivate_entry();
-            return InputResult::Modified;
-        }
-        KeyCode::Char('x') => {
-            app.input_mode = InputMode::ConfirmDelete;
-            return InputResult::Success;
-        }
-        KeyCode::Char('X') => {
-            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                app.input_mode = InputMode::ConfirmDeleteAllDropped;
-                return InputResult::Success;
-            }
-        }
-        KeyCode::Up => {
-            if let Some(pos) = current_pos {
-                if pos > 0 {
-                    app.selected_index = dropped_entries[pos - 1].0;
-                }
-            } else if !dropped_entries.is_empty() {
-                app.selected_index = dropped_entries[0].0;
-            }
-        }
-        KeyCode::Down => {
-            if let Some(pos) = current_pos {
-                if pos < dropped_entries.len() - 1 {
-                    app.selected_index = dropped_entries[pos + 1].0;
-                }
-            } else if !dropped_entries.is_empty() {
-                app.selected_index = dropped_entries[0].0;
-            }
-        }
-        KeyCode::Left | KeyCode::Right => {
-            if app.dropped_is_two_column {
-                if let Some(pos) = current_pos {
-                    let mid_point = (dropped_entries.len() + 1) / 2;
-                    let is_left = pos < mid_point;
+/// The command palette edits like the input bar, with tab completion and history.
+fn command_actions(key: KeyEvent) -> Vec<Action> {
+    let action = match key.code {
+        KeyCode::Tab => Action::CompleteCommand,
+        KeyCode::Up => Action::HistoryPrev,
+        KeyCode::Down => Action::HistoryNext,
+        _ => return text_input_actions(key),
+    };
+    vec![action]
+}
 
-                    let target_pos = if is_left {
-                        pos + mid_point
-                    } else {
-                        pos - mid_point
-                    };
+/// Typing narrows the board as you go; up/down move through the matches.
+fn filter_actions(key: KeyEvent) -> Vec<Action> {
+    let action = match key.code {
+        KeyCode::Up => Action::SelectPrev,
+        KeyCode::Down => Action::SelectNext,
+        _ => return text_input_actions(key),
+    };
+    vec![action]
+}
 
-                    if target_pos < dropped_entries.len() {
-                        app.selected_index = dropped_entries[target_pos].0;
-                    }
-                }
-            }
-        }
-        _ => {}
+/// While the help popup is open, up/down and the page keys scroll it instead of the list.
+fn help_scroll_action(app: &App, action: KeyAction, times: usize) -> Option<Action> {
+    if !app.show_help {
+        return None;
     }
-    InputResult::Success
+    let times = times.min(i16::MAX as usize) as i16;
+    let half_page = (app.half_page() as i16).saturating_mul(times);
+    match action {
+        KeyAction::Up => Some(Action::ScrollHelp(-times)),
+        KeyAction::Down => Some(Action::ScrollHelp(times)),
+        KeyAction::HalfPageUp => Some(Action::ScrollHelp(-half_page)),
+        KeyAction::HalfPageDown => Some(Action::ScrollHelp(half_page)),
+        KeyAction::PageUp => Some(Action::ScrollHelp(-half_page.saturating_mul(2))),
+        KeyAction::PageDown => Some(Action::ScrollHelp(half_page.saturating_mul(2))),
+        KeyAction::First => Some(Action::ScrollHelp(i16::MIN)),
+        KeyAction::Last => Some(Action::ScrollHelp(i16::MAX)),
+        _ => None,
+    }
+}
+
+fn confirm_delete_actions(key: KeyEvent) -> Vec<Action> {
+    let action = match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => Action::ConfirmRemove,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Action::Cancel,
+        _ => return Vec::new(),
+    };
+    vec![action]
 }
 
-fn clamp_cursor(new_cursor_pos: usize, input: &str) -> usize {
-    new_cursor_pos.clamp(0, input.graphemes(true).count())
+fn dropped_mode_actions(key: KeyEvent, app: &mut App) -> Vec<Action> {
+    let Some((action, _)) = resolve_key(app, KeyContext::Dropped, key) else {
+        return Vec::new();
+    };
+    if let Some(scroll) = help_scroll_action(app, action, 1) {
+        return vec![scroll];
+    }
+    let has_dropped = !app.get_dropped_entries().is_empty();
+    let half_page = app.half_page() as isize;
+    let page = app.page() as isize;
+
+    let action = match action {
+        KeyAction::Quit => Action::Quit,
+        KeyAction::Help => Action::ToggleHelp,
+        KeyAction::Close => Action::Cancel,
+        KeyAction::ToggleTitle => Action::ToggleFullTitle,
+        KeyAction::Reactivate if has_dropped => Action::Reactivate,
+        KeyAction::ToggleSelect if has_dropped => Action::ToggleSelected,
+        KeyAction::SelectRange if has_dropped => Action::SelectRange(app.selected_index),
+        KeyAction::Remove if has_dropped => Action::RequestRemove,
+        KeyAction::RemoveAllDropped if has_dropped => Action::RequestRemoveAllDropped,
+        KeyAction::Up if has_dropped => Action::SelectPrev,
+        KeyAction::Down if has_dropped => Action::SelectNext,
+        KeyAction::Left if has_dropped => Action::SelectPrevColumn,
+        KeyAction::Right if has_dropped => Action::SelectNextColumn,
+        KeyAction::First if has_dropped => Action::SelectRow(Some(0)),
+        KeyAction::Last if has_dropped => Action::SelectRow(None),
+        KeyAction::HalfPageDown if has_dropped => Action::MoveSelection(half_page),
+        KeyAction::HalfPageUp if has_dropped => Action::MoveSelection(-half_page),
+        KeyAction::PageDown if has_dropped => Action::MoveSelection(page),
+        KeyAction::PageUp if has_dropped => Action::MoveSelection(-page),
+        _ => return Vec::new(),
+    };
+    vec![action]
 }
 
 fn handle_mouse(mouse: MouseEvent, app: &mut App) -> InputResult {
     app.mouse_pos = (mouse.column, mouse.row);
+    let actions = mouse_actions(mouse, app);
+    dispatch_all(app, actions)
+}
 
+fn mouse_actions(mouse: MouseEvent, app: &App) -> Vec<Action> {
     match mouse.kind {
         MouseEventKind::Down(_) => {
-            if let InputMode::Adding | InputMode::Editing = app.input_mode {
-                if !app.layout.is_empty()
-                    && app.layout.len() > 1
-                    && mouse.row == app.layout[1].y + 1
-                    && mouse.column > app.layout[1].x
-                    && mouse.column < app.layout[1].x + app.layout[1].width - 1
-                {
-                    let new_cursor_pos = (mouse.column - (app.layout[1].x + 1)) as usize;
-                    app.cursor_position = clamp_cursor(new_cursor_pos, &app.input);
-                    return InputResult::Success;
+            if app.input_mode.uses_input_bar()
+                && app.layout.len() > 1
+                && mouse.row == app.layout[1].y + 1
+                && mouse.column > app.layout[1].x
+                && mouse.column < app.layout[1].x + app.layout[1].width - 1
+            {
+                let mut new_cursor_pos = (mouse.column - (app.layout[1].x + 1)) as usize;
+                if app.input_mode == InputMode::Command {
+                    // Skip the `:` prompt.
+                    new_cursor_pos = new_cursor_pos.saturating_sub(1);
                 }
+                return vec![Action::SetCursor(new_cursor_pos)];
             }
 
             if app.input_mode == InputMode::Dropped {
                 if let Some(selected_index) = crate::ui::get_dropped_mouse_selection(app) {
-                    app.selected_index = selected_index;
-                    return InputResult::Modified;
+                    if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                        return vec![Action::SelectRange(selected_index)];
+                    }
+                    return vec![Action::Select(selected_index)];
                 }
-            } else if !app.column_layout.is_empty() {
-                let col = app.column_layout.iter().position(|&r| {
-                    mouse.column >= r.x
-                        && mouse.column < r.x + r.width
-                        && mouse.row >= r.y
-                        && mouse.row < r.y + r.height
-                });
-
-                if let Some(col) = col {
-                    let status = match col {
-                        0 => Some(Status::Planning),
-                        1 => Some(Status::Watching),
-                        2 => Some(Status::Completed),
-                        _ => None,
-                    };
-
-                    if let Some(status) = status {
-                        let entry_in_status: Vec<_> = app
-                            .entry
-                            .iter()
-                            .enumerate()
-                            .filter(|(_, s)| s.status == status)
-                            .collect();
-
-                        if let Some(item_index) =
-                            (mouse.row as usize).checked_sub(app.column_layout[col].y as usize + 1)
-                        {
-                            if let Some((idx, _)) = entry_in_status.get(item_index) {
-                                app.selected_index = *idx;
-                                app.dragged_entry = Some((*idx, app.entry[*idx].status));
-                            }
-                        }
+            } else {
+                if let Some(status) = crate::ui::get_mouse_tab(app) {
+                    return vec![Action::SelectColumn(status)];
+                }
+                if let Some(idx) = crate::ui::get_mouse_selection(app) {
+                    if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                        return vec![Action::SelectRange(idx)];
                     }
+                    return vec![Action::StartDrag(idx)];
                 }
             }
+            Vec::new()
         }
-        MouseEventKind::Up(_) => {
-            if let Some((dragged_idx, _)) = app.dragged_entry {
-                if !app.column_layout.is_empty() {
-                    let col = app
-                        .column_layout
-                        .iter()
-                        .position(|&r| mouse.column >= r.x && mouse.column < r.x + r.width);
-
-                    if let Some(col) = col {
-                        let new_status = match col {
-                            0 => Some(Status::Planning),
-                            1 => Some(Status::Watching),
-                            2 => Some(Status::Completed),
-                            _ => None,
-                        };
-
-                        if let Some(new_status) = new_status {
-                            app.entry[dragged_idx].status = new_status;
-                            app.dragged_entry = None;
-                            return InputResult::Modified;
-                        }
-                    }
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            let rows = if mouse.kind == MouseEventKind::ScrollDown {
+                consts::MOUSE_SCROLL_ROWS
+            } else {
+                -consts::MOUSE_SCROLL_ROWS
+            };
+            let action = if app.show_help {
+                Some(Action::ScrollHelp(rows as i16))
+            } else {
+                match app.input_mode {
+                    InputMode::ConfirmImport => Some(Action::ScrollImportPreview(rows as i16)),
+                    InputMode::Dropped => crate::ui::get_dropped_mouse_column(app)
+                        .map(|col| Action::ScrollDropped(col, rows)),
+                    InputMode::Normal | InputMode::Filter => crate::ui::get_mouse_column(app)
+                        .map(|status| Action::ScrollColumn(status, rows)),
+                    _ => None,
                 }
-                app.dragged_entry = None;
-            }
+            };
+            action.into_iter().collect()
         }
-        _ => {}
+        MouseEventKind::Up(_) if app.dragged_entry.is_some() => {
+            vec![Action::DropDragged(crate::ui::get_mouse_column(app))]
+        }
+        _ => Vec::new(),
     }
-    InputResult::Success
 }
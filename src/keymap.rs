@@ -0,0 +1,743 @@
+use crate::config::edit_distance;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a key press is handled. Each context has its own set of actions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyContext {
+    Normal,
+    Dropped,
+    Episodes,
+}
+
+impl fmt::Display for KeyContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            KeyContext::Normal => "normal mode",
+            KeyContext::Dropped => "the dropped list",
+            KeyContext::Episodes => "the episodes popup",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The set of default keys the `keys` section starts from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyProfile {
+    #[default]
+    Default,
+    /// `hjkl` movement, `g g`/`G` and `?` for help.
+    Vim,
+}
+
+/// Something a key can be bound to in the `keys` section of `config.yaml`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyAction {
+    Add,
+    Edit,
+    Dropped,
+    Episodes,
+    Export,
+    Import,
+    Command,
+    Filter,
+    Sort,
+    ToggleSelect,
+    SelectRange,
+    ClearSelection,
+    NextEpisode,
+    PrevEpisode,
+    NextSeason,
+    Remove,
+    Up,
+    Down,
+    Left,
+    Right,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    First,
+    Last,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    ToggleTitle,
+    Details,
+    Stats,
+    Activity,
+    Help,
+    Quit,
+    Close,
+    Reactivate,
+    RemoveAllDropped,
+    WatchedUp,
+    WatchedDown,
+    SetMaxEpisodes,
+    SetRuntime,
+    SetSeasonRuntime,
+    SetAirDate,
+    PlanSession,
+    ClearSessions,
+}
+
+use KeyContext::{Dropped as InDropped, Episodes as InEpisodes, Normal as InNormal};
+
+impl KeyAction {
+    /// Every action, in the order they are listed in the help popup.
+    pub const ALL: [KeyAction; 47] = [
+        KeyAction::Add,
+        KeyAction::Edit,
+        KeyAction::Dropped,
+        KeyAction::Episodes,
+        KeyAction::Export,
+        KeyAction::Import,
+        KeyAction::Command,
+        KeyAction::Filter,
+        KeyAction::Sort,
+        KeyAction::ToggleSelect,
+        KeyAction::SelectRange,
+        KeyAction::ClearSelection,
+        KeyAction::NextEpisode,
+        KeyAction::PrevEpisode,
+        KeyAction::NextSeason,
+        KeyAction::Remove,
+        KeyAction::Up,
+        KeyAction::Down,
+        KeyAction::Left,
+        KeyAction::Right,
+        KeyAction::MoveUp,
+        KeyAction::MoveDown,
+        KeyAction::MoveLeft,
+        KeyAction::MoveRight,
+        KeyAction::First,
+        KeyAction::Last,
+        KeyAction::HalfPageDown,
+        KeyAction::HalfPageUp,
+        KeyAction::PageDown,
+        KeyAction::PageUp,
+        KeyAction::ToggleTitle,
+        KeyAction::Details,
+        KeyAction::Stats,
+        KeyAction::Activity,
+        KeyAction::Help,
+        KeyAction::Quit,
+        KeyAction::Close,
+        KeyAction::Reactivate,
+        KeyAction::RemoveAllDropped,
+        KeyAction::WatchedUp,
+        KeyAction::WatchedDown,
+        KeyAction::SetMaxEpisodes,
+        KeyAction::SetRuntime,
+        KeyAction::SetSeasonRuntime,
+        KeyAction::SetAirDate,
+        KeyAction::PlanSession,
+        KeyAction::ClearSessions,
+    ];
+
+    /// Name used in `config.yaml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Add => "add",
+            KeyAction::Edit => "edit",
+            KeyAction::Dropped => "dropped",
+            KeyAction::Episodes => "episodes",
+            KeyAction::Export => "export",
+            KeyAction::Import => "import",
+            KeyAction::Command => "command",
+            KeyAction::Filter => "filter",
+            KeyAction::Sort => "sort",
+            KeyAction::ToggleSelect => "toggle_select",
+            KeyAction::SelectRange => "select_range",
+            KeyAction::ClearSelection => "clear_selection",
+            KeyAction::NextEpisode => "next_episode",
+            KeyAction::PrevEpisode => "prev_episode",
+            KeyAction::NextSeason => "next_season",
+            KeyAction::Remove => "remove",
+            KeyAction::Up => "up",
+            KeyAction::Down => "down",
+            KeyAction::Left => "left",
+            KeyAction::Right => "right",
+            KeyAction::MoveUp => "move_up",
+            KeyAction::MoveDown => "move_down",
+            KeyAction::MoveLeft => "move_left",
+            KeyAction::MoveRight => "move_right",
+            KeyAction::First => "first",
+            KeyAction::Last => "last",
+            KeyAction::HalfPageDown => "half_page_down",
+            KeyAction::HalfPageUp => "half_page_up",
+            KeyAction::PageDown => "page_down",
+            KeyAction::PageUp => "page_up",
+            KeyAction::ToggleTitle => "toggle_title",
+            KeyAction::Details => "details",
+            KeyAction::Stats => "stats",
+            KeyAction::Activity => "activity",
+            KeyAction::Help => "help",
+            KeyAction::Quit => "quit",
+            KeyAction::Close => "close",
+            KeyAction::Reactivate => "reactivate",
+            KeyAction::RemoveAllDropped => "remove_all_dropped",
+            KeyAction::WatchedUp => "watched_up",
+            KeyAction::WatchedDown => "watched_down",
+            KeyAction::SetMaxEpisodes => "set_max_episodes",
+            KeyAction::SetRuntime => "set_runtime",
+            KeyAction::SetSeasonRuntime => "set_season_runtime",
+            KeyAction::SetAirDate => "set_air_date",
+            KeyAction::PlanSession => "plan_session",
+            KeyAction::ClearSessions => "clear_sessions",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<KeyAction> {
+        KeyAction::ALL
+            .into_iter()
+            .find(|action| action.name() == name)
+    }
+
+    /// Shown next to the keys in the help popup.
+    pub fn description(self) -> &'static str {
+        match self {
+            KeyAction::Add => "add new entry",
+            KeyAction::Edit => "edit entry",
+            KeyAction::Dropped => "show dropped",
+            KeyAction::Episodes => "total episodes",
+            KeyAction::Export => "export",
+            KeyAction::Import => "import",
+            KeyAction::Command => "command palette",
+            KeyAction::Filter => "filter",
+            KeyAction::Sort => "cycle column sort",
+            KeyAction::ToggleSelect => "select for bulk",
+            KeyAction::SelectRange => "select range",
+            KeyAction::ClearSelection => "clear selection",
+            KeyAction::NextEpisode => "increase episode",
+            KeyAction::PrevEpisode => "decrease episode",
+            KeyAction::NextSeason => "increase season",
+            KeyAction::Remove => "remove entry",
+            KeyAction::Up => "previous row",
+            KeyAction::Down => "next row",
+            KeyAction::Left => "previous column",
+            KeyAction::Right => "next column",
+            KeyAction::MoveUp => "move entry up",
+            KeyAction::MoveDown => "move entry down",
+            KeyAction::MoveLeft => "move to previous status",
+            KeyAction::MoveRight => "move to next status",
+            KeyAction::First => "first in column",
+            KeyAction::Last => "last in column",
+            KeyAction::HalfPageDown => "half page down",
+            KeyAction::HalfPageUp => "half page up",
+            KeyAction::PageDown => "page down",
+            KeyAction::PageUp => "page up",
+            KeyAction::ToggleTitle => "full title",
+            KeyAction::Details => "detail pane",
+            KeyAction::Stats => "statistics",
+            KeyAction::Activity => "activity calendar",
+            KeyAction::Help => "toggle help",
+            KeyAction::Quit => "quit",
+            KeyAction::Close => "close",
+            KeyAction::Reactivate => "reactivate entry",
+            KeyAction::RemoveAllDropped => "remove all dropped",
+            KeyAction::WatchedUp => "increase watched",
+            KeyAction::WatchedDown => "decrease watched",
+            KeyAction::SetMaxEpisodes => "set max episodes",
+            KeyAction::SetRuntime => "set runtime",
+            KeyAction::SetSeasonRuntime => "set season runtime",
+            KeyAction::SetAirDate => "set air date",
+            KeyAction::PlanSession => "plan session",
+            KeyAction::ClearSessions => "clear sessions",
+        }
+    }
+
+    pub fn default_keys(self, profile: KeyProfile) -> &'static [&'static str] {
+        if profile == KeyProfile::Vim {
+            let keys: &[&str] = match self {
+                KeyAction::Up => &["k", "up"],
+                KeyAction::Down => &["j", "down"],
+                KeyAction::Left => &["h", "left"],
+                KeyAction::Right => &["l", "right"],
+                KeyAction::MoveUp => &["K", "shift+up"],
+                KeyAction::MoveDown => &["J", "shift+down"],
+                KeyAction::MoveLeft => &["H", "shift+left"],
+                KeyAction::MoveRight => &["L", "shift+right"],
+                KeyAction::First => &["g g", "home"],
+                KeyAction::Last => &["G", "end"],
+                KeyAction::PageDown => &["ctrl+f", "pagedown"],
+                KeyAction::PageUp => &["ctrl+b", "pageup"],
+                KeyAction::Help => &["?"],
+                _ => &[],
+            };
+            if !keys.is_empty() {
+                return keys;
+            }
+        }
+
+        match self {
+            KeyAction::Add => &["a"],
+            KeyAction::Edit => &["e"],
+            KeyAction::Dropped => &["d"],
+            KeyAction::Episodes => &["o"],
+            KeyAction::Export => &["E"],
+            KeyAction::Import => &["I"],
+            KeyAction::Command => &[":"],
+            KeyAction::Filter => &["/"],
+            KeyAction::Sort => &["s"],
+            KeyAction::ToggleSelect => &["space"],
+            KeyAction::SelectRange => &["V"],
+            KeyAction::ClearSelection => &["esc"],
+            KeyAction::NextEpisode => &["+"],
+            KeyAction::PrevEpisode => &["-"],
+            KeyAction::NextSeason => &["#"],
+            KeyAction::Remove => &["x"],
+            KeyAction::Up => &["up"],
+            KeyAction::Down => &["down"],
+            KeyAction::Left => &["left"],
+            KeyAction::Right => &["right"],
+            KeyAction::MoveUp => &["shift+up"],
+            KeyAction::MoveDown => &["shift+down"],
+            KeyAction::MoveLeft => &["shift+left"],
+            KeyAction::MoveRight => &["shift+right"],
+            KeyAction::First => &["home"],
+            KeyAction::Last => &["end"],
+            KeyAction::HalfPageDown => &["ctrl+d"],
+            KeyAction::HalfPageUp => &["ctrl+u"],
+            KeyAction::PageDown => &["pagedown"],
+            KeyAction::PageUp => &["pageup"],
+            KeyAction::ToggleTitle => &["t"],
+            KeyAction::Details => &["i"],
+            KeyAction::Stats => &["S"],
+            KeyAction::Activity => &["A"],
+            KeyAction::Help => &["h"],
+            KeyAction::Quit => &["q"],
+            KeyAction::Close => &["esc", "enter"],
+            KeyAction::Reactivate => &["r"],
+            KeyAction::RemoveAllDropped => &["X"],
+            KeyAction::WatchedUp => &["+"],
+            KeyAction::WatchedDown => &["-"],
+            KeyAction::SetMaxEpisodes => &["#"],
+            KeyAction::SetRuntime => &["r"],
+            KeyAction::SetSeasonRuntime => &["R"],
+            KeyAction::SetAirDate => &["d"],
+            KeyAction::PlanSession => &["w"],
+            KeyAction::ClearSessions => &["W"],
+        }
+    }
+
+    pub fn contexts(self) -> &'static [KeyContext] {
+        match self {
+            KeyAction::Add
+            | KeyAction::Edit
+            | KeyAction::Dropped
+            | KeyAction::Export
+            | KeyAction::Import
+            | KeyAction::Command
+            | KeyAction::Filter
+            | KeyAction::Sort
+            | KeyAction::ClearSelection
+            | KeyAction::NextEpisode
+            | KeyAction::PrevEpisode
+            | KeyAction::NextSeason
+            | KeyAction::MoveUp
+            | KeyAction::MoveDown
+            | KeyAction::MoveLeft
+            | KeyAction::MoveRight
+            | KeyAction::Details
+            | KeyAction::Stats
+            | KeyAction::Activity => &[InNormal],
+            KeyAction::Episodes => &[InNormal, InEpisodes],
+            KeyAction::Remove
+            | KeyAction::Up
+            | KeyAction::Down
+            | KeyAction::Left
+            | KeyAction::Right
+            | KeyAction::ToggleTitle
+            | KeyAction::Help
+            | KeyAction::ToggleSelect
+            | KeyAction::SelectRange
+            | KeyAction::First
+            | KeyAction::Last
+            | KeyAction::HalfPageDown
+            | KeyAction::HalfPageUp
+            | KeyAction::PageDown
+            | KeyAction::PageUp
+            | KeyAction::Quit => &[InNormal, InDropped],
+            KeyAction::Close => &[InDropped, InEpisodes],
+            KeyAction::Reactivate | KeyAction::RemoveAllDropped => &[InDropped],
+            KeyAction::WatchedUp
+            | KeyAction::WatchedDown
+            | KeyAction::SetMaxEpisodes
+            | KeyAction::SetRuntime
+            | KeyAction::SetSeasonRuntime
+            | KeyAction::SetAirDate
+            | KeyAction::PlanSession
+            | KeyAction::ClearSessions => &[InEpisodes],
+        }
+    }
+}
+
+impl fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Serialize for KeyAction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyAction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        KeyAction::from_name(&name).ok_or_else(|| {
+            let suggestion = KeyAction::ALL
+                .into_iter()
+                .map(|action| (edit_distance(&name, action.name()), action))
+                .filter(|(distance, _)| *distance <= 2)
+                .min_by_key(|(distance, _)| *distance);
+            let message = match suggestion {
+                Some((_, action)) => {
+                    format!("unknown action '{}', did you mean '{}'?", name, action)
+                }
+                None => format!("unknown action '{}'", name),
+            };
+            de::Error::custom(message)
+        })
+    }
+}
+
+/// A key with its modifiers, written like `q`, `#`, `shift+up` or `ctrl+d`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Terminals report shifted characters both as the shifted character and with
+    /// `SHIFT` set, so the modifier is dropped for characters.
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        KeyChord { code, modifiers }
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        KeyChord::normalized(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let (modifier_names, key) = if let Some(prefix) = text.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            match text.rsplit_once('+') {
+                Some((prefix, key)) if !key.is_empty() => (prefix, key),
+                _ => ("", text),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{}' in key '{}'", name, text)),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key '{}'", text)),
+                },
+            },
+        };
+
+        Ok(KeyChord::normalized(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            other => write!(f, "{}", format!("{:?}", other).to_lowercase()),
+        }
+    }
+}
+
+/// One or more keys pressed in order, written separated by spaces like `g g`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBinding(pub Vec<KeyChord>);
+
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let chords = text
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<KeyChord>, String>>()?;
+        if chords.is_empty() {
+            return Err("empty key".to_string());
+        }
+        Ok(KeyBinding(chords))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chords: Vec<String> = self.0.iter().map(|chord| chord.to_string()).collect();
+        write!(f, "{}", chords.join(" "))
+    }
+}
+
+/// The keys bound to one action: a single key or a list of keys.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyBindings(pub Vec<KeyBinding>);
+
+impl<'de> Deserialize<'de> for KeyBindings {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(KeyBindingsVisitor)
+    }
+}
+
+struct KeyBindingsVisitor;
+
+impl<'de> Visitor<'de> for KeyBindingsVisitor {
+    type Value = KeyBindings;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a key or a list of keys")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        value
+            .parse()
+            .map(|binding| KeyBindings(vec![binding]))
+            .map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        self.visit_str(&value.to_string())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bindings = Vec::new();
+        while let Some(key) = seq.next_element::<String>()? {
+            bindings.push(key.parse().map_err(de::Error::custom)?);
+        }
+        Ok(KeyBindings(bindings))
+    }
+}
+
+impl Serialize for KeyBindings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|binding| binding.to_string()))
+    }
+}
+
+/// The `keys` section: a profile plus replacement keys for some actions; the rest keep
+/// the profile's defaults.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyConfig {
+    pub profile: KeyProfile,
+    #[serde(flatten)]
+    pub bindings: BTreeMap<KeyAction, KeyBindings>,
+}
+
+/// Two actions available in the same context whose keys clash, either because they are
+/// the same or because one starts the other.
+#[derive(Debug, PartialEq)]
+pub struct KeyConflict {
+    pub binding: KeyBinding,
+    pub context: KeyContext,
+    pub actions: (KeyAction, KeyAction),
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "key '{}' is bound to both '{}' and '{}' in {}",
+            self.binding, self.actions.0, self.actions.1, self.context
+        )
+    }
+}
+
+/// Result of looking up the keys pressed so far.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyMatch {
+    Action(KeyAction),
+    /// The keys start a longer binding; wait for the next key.
+    Pending,
+    None,
+}
+
+/// The active key bindings: the profile defaults with the `keys` section applied.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: BTreeMap<KeyAction, Vec<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn new(config: &KeyConfig) -> Result<Self, KeyConflict> {
+        let bindings: BTreeMap<KeyAction, Vec<KeyBinding>> = KeyAction::ALL
+            .into_iter()
+            .map(|action| {
+                let bindings = match config.bindings.get(&action) {
+                    Some(bindings) => bindings.0.clone(),
+                    None => action
+                        .default_keys(config.profile)
+                        .iter()
+                        .map(|key| key.parse().expect("default keys are valid"))
+                        .collect(),
+                };
+                (action, bindings)
+            })
+            .collect();
+
+        for context in [InNormal, InDropped, InEpisodes] {
+            let mut seen: Vec<(&KeyBinding, KeyAction)> = Vec::new();
+            for (action, keys) in &bindings {
+                if !action.contexts().contains(&context) {
+                    continue;
+                }
+                for binding in keys {
+                    let clash = seen.iter().find(|(other, other_action)| {
+                        other_action != action
+                            && (binding.0.starts_with(&other.0) || other.0.starts_with(&binding.0))
+                    });
+                    if let Some((other, other_action)) = clash {
+                        let shorter = if other.0.len() <= binding.0.len() {
+                            *other
+                        } else {
+                            binding
+                        };
+                        return Err(KeyConflict {
+                            binding: shorter.clone(),
+                            context,
+                            actions: (*other_action, *action),
+                        });
+                    }
+                    seen.push((binding, *action));
+                }
+            }
+        }
+
+        Ok(Keymap { bindings })
+    }
+
+    /// The action bound to the keys pressed so far in `context`.
+    pub fn lookup(&self, context: KeyContext, keys: &[KeyChord]) -> KeyMatch {
+        let mut pending = false;
+        for (action, bindings) in &self.bindings {
+            if !action.contexts().contains(&context) {
+                continue;
+            }
+            for binding in bindings {
+                if binding.0 == keys {
+                    return KeyMatch::Action(*action);
+                }
+                pending |= binding.0.starts_with(keys);
+            }
+        }
+        if pending {
+            KeyMatch::Pending
+        } else {
+            KeyMatch::None
+        }
+    }
+
+    /// The action `key` is bound to on its own in `context`.
+    pub fn action(&self, context: KeyContext, key: KeyEvent) -> Option<KeyAction> {
+        match self.lookup(context, &[KeyChord::from(key)]) {
+            KeyMatch::Action(action) => Some(action),
+            _ => None,
+        }
+    }
+
+    pub fn bindings(&self, action: KeyAction) -> &[KeyBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The keys of `action` joined with `/`, e.g. `esc/enter`.
+    pub fn describe(&self, action: KeyAction) -> String {
+        let keys: Vec<String> = self
+            .bindings(action)
+            .iter()
+            .map(|binding| binding.to_string())
+            .collect();
+        if keys.is_empty() {
+            "unbound".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+
+    /// `keys: description` lines for every action in `context`, in help order.
+    pub fn help_lines(&self, context: KeyContext) -> Vec<String> {
+        KeyAction::ALL
+            .into_iter()
+            .filter(|action| action.contexts().contains(&context))
+            .map(|action| format!("{}: {}", self.describe(action), action.description()))
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&KeyConfig::default()).expect("default keys don't conflict")
+    }
+}
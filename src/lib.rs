@@ -1,8 +1,13 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use uuid::Uuid;
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
 pub enum Status {
+    #[default]
     Planning,
     Watching,
     Completed,
@@ -10,6 +15,9 @@ pub enum Status {
 }
 
 impl Status {
+    /// The board columns, left to right.
+    pub const BOARD: [Status; 3] = [Status::Planning, Status::Watching, Status::Completed];
+
     pub fn next(&self) -> Self {
         match self {
             Status::Planning => Status::Watching,
@@ -29,14 +37,15 @@ impl Status {
     }
 }
 
-impl ToString for Status {
-    fn to_string(&self) -> String {
-        match self {
-            Status::Planning => "Planning".to_string(),
-            Status::Watching => "Watching".to_string(),
-            Status::Completed => "Completed".to_string(),
-            Status::Dropped => "Dropped".to_string(),
-        }
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Status::Planning => "Planning",
+            Status::Watching => "Watching",
+            Status::Completed => "Completed",
+            Status::Dropped => "Dropped",
+        };
+        f.write_str(name)
     }
 }
 
@@ -52,7 +61,7 @@ impl From<String> for Status {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Entry {
     pub id: Uuid,
     pub title: String,
@@ -61,12 +70,154 @@ pub struct Entry {
     pub status: Status,
     pub watched_episodes: u32,
     pub max_episodes: u32,
+    /// Episode runtime in minutes, `0` if unknown.
+    #[serde(default)]
+    pub runtime_minutes: u32,
+    /// Per-season runtime overrides in minutes, keyed by season number.
+    #[serde(default)]
+    pub season_runtimes: BTreeMap<u32, u32>,
+    /// Air date of the next unwatched episode; later episodes are assumed to air weekly.
+    #[serde(default)]
+    pub next_air_date: Option<NaiveDate>,
+    /// Planned watch sessions in local time.
+    #[serde(default)]
+    pub watch_sessions: Vec<NaiveDateTime>,
+    /// When the entry was added or last changed, in local time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+    /// Free-form labels like `anime`, kept lowercase and sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Episodes watched on each day in local time, as counted by `+`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub watch_log: BTreeMap<NaiveDate, u32>,
+}
+
+impl Entry {
+    /// Whether two entries hold the same watchlist data, ignoring when they were updated.
+    pub fn same_content(&self, other: &Entry) -> bool {
+        Entry {
+            updated_at: other.updated_at,
+            ..self.clone()
+        } == *other
+    }
+
+    /// Stamps the entry as updated now. Called wherever the app changes an entry.
+    pub fn touch(&mut self) {
+        self.updated_at = Some(database::now());
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Adds `tag` (lowercased) unless the entry has it already. Returns whether it was added.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || self.has_tag(&tag) {
+            return false;
+        }
+        self.tags.push(tag);
+        self.tags.sort();
+        true
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim().to_lowercase();
+        let before = self.tags.len();
+        self.tags.retain(|t| *t != tag);
+        self.tags.len() != before
+    }
+
+    /// Records `episodes` watched on `date`.
+    pub fn log_watched(&mut self, date: NaiveDate, episodes: u32) {
+        if episodes > 0 {
+            *self.watch_log.entry(date).or_insert(0) += episodes;
+        }
+    }
+
+    /// Takes back up to `episodes` recorded on `date`, e.g. after pressing `+` once too often.
+    pub fn unlog_watched(&mut self, date: NaiveDate, episodes: u32) {
+        if let Some(count) = self.watch_log.get_mut(&date) {
+            *count = count.saturating_sub(episodes);
+            if *count == 0 {
+                self.watch_log.remove(&date);
+            }
+        }
+    }
+
+    /// Episodes left to watch, or `None` while the total is unknown.
+    pub fn episodes_remaining(&self) -> Option<u32> {
+        if self.status == Status::Completed {
+            return Some(0);
+        }
+        if self.max_episodes == 0 {
+            return None;
+        }
+        Some(self.max_episodes.saturating_sub(self.watched_episodes))
+    }
+
+    /// Share of episodes watched in percent; completed entries always count as 100.
+    pub fn progress(&self) -> u16 {
+        if self.status == Status::Completed {
+            return 100;
+        }
+
+        if self.max_episodes == 0 {
+            return 0;
+        }
+
+        ((self.watched_episodes.saturating_mul(100)) / self.max_episodes).min(100) as u16
+    }
+
+    /// Runtime of an episode in `season`, falling back to the entry-wide runtime.
+    pub fn runtime_for_season(&self, season: u32) -> u32 {
+        self.season_runtimes
+            .get(&season)
+            .copied()
+            .unwrap_or(self.runtime_minutes)
+    }
+
+    /// Estimated time spent on the watched episodes. Watched episodes aren't tracked per
+    /// season, so all of them are counted at the current season's runtime; earlier seasons
+    /// with a different runtime make this an approximation.
+    pub fn minutes_watched(&self) -> u64 {
+        self.watched_episodes as u64 * self.runtime_for_season(self.season) as u64
+    }
+
+    /// Estimated time left until all episodes are watched.
+    ///
+    /// Returns `None` while the total number of episodes is unknown.
+    pub fn minutes_remaining(&self) -> Option<u64> {
+        let remaining = self.episodes_remaining()? as u64;
+        Some(remaining * self.runtime_for_season(self.season) as u64)
+    }
+}
+
+/// Formats a duration in minutes as e.g. `2h 05m` or `45m`.
+pub fn format_minutes(minutes: u64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    }
 }
 
+pub mod action;
 pub mod app;
+pub mod cli;
+pub mod command;
 pub mod config;
 pub mod consts;
+pub mod formats;
+pub mod fuzzy;
+pub mod import;
 pub mod input;
+pub mod keymap;
+pub mod scroll;
+pub mod sort;
+pub mod stats;
+pub mod theme;
 
 pub mod database;
 pub mod ui;
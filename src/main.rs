@@ -1,24 +1,40 @@
+use clap::Parser;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use senarai::{app::App, config, database, input, ui};
+use senarai::{app::App, cli, config, database, input, keymap::Keymap, ui};
 use std::io::{self, stdout};
+use std::process;
 use std::time::Instant;
 
 fn main() -> io::Result<()> {
+    let args = cli::Cli::parse();
+    if let Some(command) = args.command {
+        if let Err(e) = cli::run(command, &args.config) {
+            eprintln!("senarai: {}", e);
+            process::exit(e.exit_code());
+        }
+        return Ok(());
+    }
+
     stdout().execute(EnterAlternateScreen)?;
     stdout().execute(EnableMouseCapture)?;
+    stdout().execute(EnableBracketedPaste)?;
     enable_raw_mode()?;
 
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     terminal.clear()?;
 
-    let (config, config_error) = match config::load_config() {
+    let (config, config_error) = match config::load_config(&args.config) {
         Ok(config) => (config, None),
-        Err(e) => (config::Config::default(), Some(e)),
+        Err(e) => {
+            let mut config = config::Config::default();
+            config.apply_overrides(&args.config);
+            (config, Some(e))
+        }
     };
 
     if let Err(e) = database::init_db(&config) {
@@ -37,8 +53,19 @@ fn main() -> io::Result<()> {
         }
     };
     let mut app = App::new(entry, config.clone());
+    match database::load_sort_modes(&config) {
+        Ok(modes) => app.sort_modes = modes.into_iter().collect(),
+        Err(e) => {
+            app.error = Some(e.to_string());
+            app.last_error_time = Some(Instant::now());
+        }
+    }
 
-    if let Some(e) = config_error {
+    // Clashing keys fall back to the defaults (see `App::new`) but keep the rest of the config.
+    let key_error = Keymap::new(&config.keys)
+        .err()
+        .map(|e| format!("Invalid keys, using the defaults: {}", e));
+    if let Some(e) = config_error.or(key_error) {
         app.error = Some(e);
         app.last_error_time = Some(Instant::now());
     }
@@ -63,6 +90,7 @@ fn main() -> io::Result<()> {
 
     stdout().execute(LeaveAlternateScreen)?;
     stdout().execute(DisableMouseCapture)?;
+    stdout().execute(DisableBracketedPaste)?;
     disable_raw_mode()?;
 
     Ok(())
@@ -0,0 +1,39 @@
+use crate::consts::SCROLL_OFF;
+
+/// Rows of context kept around the selection in a view `height` rows tall.
+fn padding(height: usize) -> usize {
+    SCROLL_OFF.min(height.saturating_sub(1) / 2)
+}
+
+/// The first row to show of a list of `len` rows seen `height` rows at a time. Starts from
+/// the previous `offset` and only moves it as far as needed to show `selected` with
+/// `SCROLL_OFF` rows of context, so the list doesn't jump while the selection moves.
+pub fn offset_for(offset: usize, selected: Option<usize>, len: usize, height: usize) -> usize {
+    let max_offset = len.saturating_sub(height);
+    let mut offset = offset.min(max_offset);
+    if let (Some(selected), true) = (selected, height > 0) {
+        let pad = padding(height);
+        if selected < offset + pad {
+            offset = selected.saturating_sub(pad);
+        } else if selected + pad + 1 > offset + height {
+            offset = selected + pad + 1 - height;
+        }
+    }
+    offset.min(max_offset)
+}
+
+/// The row nearest to `selected` that `offset_for` would show without moving `offset`, for
+/// dragging the selection along when the view is scrolled.
+pub fn selection_in_view(selected: usize, offset: usize, len: usize, height: usize) -> usize {
+    if len == 0 || height == 0 {
+        return selected;
+    }
+    let pad = padding(height);
+    let low = if offset == 0 { 0 } else { offset + pad };
+    let high = if offset + height >= len {
+        len - 1
+    } else {
+        offset + height - 1 - pad
+    };
+    selected.clamp(low.min(high), high)
+}
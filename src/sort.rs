@@ -0,0 +1,92 @@
+use crate::Entry;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// How the entries of a board column are ordered. Sorting only changes what is shown; the
+/// manual order kept in the database stays as it was.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortMode {
+    /// The order set with move up/down.
+    #[default]
+    Manual,
+    /// Alphabetically, ignoring a leading "The", "A" or "An".
+    Title,
+    /// Most watched (in percent) first.
+    Progress,
+    /// Most recently added or changed first.
+    Updated,
+    /// Fewest episodes left first; entries without a known total come last.
+    Remaining,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 5] = [
+        SortMode::Manual,
+        SortMode::Title,
+        SortMode::Progress,
+        SortMode::Updated,
+        SortMode::Remaining,
+    ];
+
+    /// Name used by `:sort` and in the database.
+    pub fn name(self) -> &'static str {
+        match self {
+            SortMode::Manual => "manual",
+            SortMode::Title => "title",
+            SortMode::Progress => "progress",
+            SortMode::Updated => "updated",
+            SortMode::Remaining => "remaining",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<SortMode> {
+        SortMode::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The mode after this one, wrapping around to manual.
+    pub fn next(self) -> SortMode {
+        let pos = SortMode::ALL.iter().position(|m| *m == self).unwrap_or(0);
+        SortMode::ALL[(pos + 1) % SortMode::ALL.len()]
+    }
+
+    /// Sorts a column given in manual order. Ties keep their manual order.
+    pub fn sort(self, entries: &mut [(usize, &Entry)]) {
+        match self {
+            SortMode::Manual => {}
+            SortMode::Title => entries.sort_by_cached_key(|(_, e)| title_sort_key(&e.title)),
+            SortMode::Progress => entries.sort_by_key(|(_, e)| std::cmp::Reverse(e.progress())),
+            SortMode::Updated => {
+                entries.sort_by_key(|(_, e)| std::cmp::Reverse(e.updated_at));
+            }
+            SortMode::Remaining => entries.sort_by(|(_, a), (_, b)| {
+                match (a.episodes_remaining(), b.episodes_remaining()) {
+                    (Some(a), Some(b)) => a.cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SortMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Lowercased title without a leading article, so "The Wire" sorts under W.
+pub fn title_sort_key(title: &str) -> String {
+    let lower = title.trim().to_lowercase();
+    for article in ["the ", "a ", "an "] {
+        if let Some(rest) = lower.strip_prefix(article) {
+            if !rest.trim().is_empty() {
+                return rest.trim_start().to_string();
+            }
+        }
+    }
+    lower
+}
@@ -0,0 +1,189 @@
+use crate::{Entry, Status};
+use chrono::{Datelike, Days, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Weeks covered by the episodes-per-week chart, including the current one.
+pub const WEEKS: usize = 12;
+
+/// How many of the longest-running shows are listed.
+pub const LONGEST_RUNNING: usize = 5;
+
+/// Weeks covered by the activity calendar, a year including the current week.
+pub const ACTIVITY_WEEKS: usize = 53;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct StatusCount {
+    pub status: Status,
+    pub entries: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ShowLength {
+    pub title: String,
+    pub episodes: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WeekCount {
+    /// The Monday the week starts on.
+    pub week: NaiveDate,
+    pub episodes: u32,
+}
+
+/// Numbers shown by the statistics popup and `senarai stats`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    pub entries: usize,
+    /// Board columns first, then Dropped.
+    pub per_status: Vec<StatusCount>,
+    pub episodes_watched: u64,
+    /// Completed entries out of all started ones (everything but Planning), in percent.
+    pub completion_rate: Option<u16>,
+    /// Mean progress of the Watching entries whose episode count is known, in percent.
+    pub watching_progress: Option<u16>,
+    /// Shows with the most episodes, longest first.
+    pub longest_running: Vec<ShowLength>,
+    /// Episodes watched per week, oldest first. Empty until an episode has been logged.
+    pub episodes_per_week: Vec<WeekCount>,
+    /// Days in a row with a watched episode, up to today.
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl Stats {
+    /// Computes the statistics of `entries`, with `today` ending the weekly chart.
+    pub fn new(entries: &[Entry], today: NaiveDate) -> Self {
+        let count = |status: Status| entries.iter().filter(|e| e.status == status).count();
+        let per_status = Status::BOARD
+            .into_iter()
+            .chain([Status::Dropped])
+            .map(|status| StatusCount {
+                status,
+                entries: count(status),
+            })
+            .collect();
+
+        let started = entries.len() - count(Status::Planning);
+        let completion_rate =
+            (started > 0).then(|| (count(Status::Completed) * 100 / started) as u16);
+
+        let watching: Vec<u16> = entries
+            .iter()
+            .filter(|e| e.status == Status::Watching && e.max_episodes > 0)
+            .map(Entry::progress)
+            .collect();
+        let watching_progress = (!watching.is_empty())
+            .then(|| (watching.iter().map(|&p| p as usize).sum::<usize>() / watching.len()) as u16);
+
+        let mut longest_running: Vec<ShowLength> = entries
+            .iter()
+            .map(|e| ShowLength {
+                title: e.title.clone(),
+                episodes: e.max_episodes.max(e.watched_episodes),
+            })
+            .filter(|show| show.episodes > 0)
+            .collect();
+        longest_running.sort_by(|a, b| {
+            b.episodes
+                .cmp(&a.episodes)
+                .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+        });
+        longest_running.truncate(LONGEST_RUNNING);
+        let days = episodes_per_day(entries);
+
+        Stats {
+            entries: entries.len(),
+            per_status,
+            episodes_watched: entries.iter().map(|e| e.watched_episodes as u64).sum(),
+            completion_rate,
+            watching_progress,
+            longest_running,
+            episodes_per_week: episodes_per_week(entries, today),
+            current_streak: current_streak(&days, today),
+            longest_streak: longest_streak(&days),
+        }
+    }
+}
+
+/// The Monday starting the week of `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+fn episodes_per_week(entries: &[Entry], today: NaiveDate) -> Vec<WeekCount> {
+    if entries.iter().all(|e| e.watch_log.is_empty()) {
+        return Vec::new();
+    }
+
+    let current = week_start(today);
+    let mut weeks: Vec<WeekCount> = (0..WEEKS as u64)
+        .rev()
+        .map(|ago| WeekCount {
+            week: current - Days::new(ago * 7),
+            episodes: 0,
+        })
+        .collect();
+    for (date, episodes) in entries.iter().flat_map(|e| &e.watch_log) {
+        let week = week_start(*date);
+        if let Some(count) = weeks.iter_mut().find(|w| w.week == week) {
+            count.episodes += episodes;
+        }
+    }
+    weeks
+}
+
+/// Episodes watched on each day, summed over all entries.
+pub fn episodes_per_day(entries: &[Entry]) -> BTreeMap<NaiveDate, u32> {
+    let mut days = BTreeMap::new();
+    for (date, episodes) in entries.iter().flat_map(|e| &e.watch_log) {
+        *days.entry(*date).or_insert(0) += episodes;
+    }
+    days
+}
+
+/// What was watched on `date`, most episodes first.
+pub fn watched_on(entries: &[Entry], date: NaiveDate) -> Vec<ShowLength> {
+    let mut shows: Vec<ShowLength> = entries
+        .iter()
+        .filter_map(|e| {
+            e.watch_log.get(&date).map(|&episodes| ShowLength {
+                title: e.title.clone(),
+                episodes,
+            })
+        })
+        .collect();
+    shows.sort_by(|a, b| b.episodes.cmp(&a.episodes).then(a.title.cmp(&b.title)));
+    shows
+}
+
+/// The run of watched days ending today. A day without episodes yet doesn't break it
+/// until it is over, so the run may also end yesterday.
+pub fn current_streak(days: &BTreeMap<NaiveDate, u32>, today: NaiveDate) -> u32 {
+    let mut day = if days.contains_key(&today) {
+        today
+    } else {
+        today - Days::new(1)
+    };
+    let mut streak = 0;
+    while days.contains_key(&day) {
+        streak += 1;
+        day = day - Days::new(1);
+    }
+    streak
+}
+
+pub fn longest_streak(days: &BTreeMap<NaiveDate, u32>) -> u32 {
+    let mut longest = 0;
+    let mut streak = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &day in days.keys() {
+        streak = match previous {
+            Some(previous) if previous + Days::new(1) == day => streak + 1,
+            _ => 1,
+        };
+        longest = longest.max(streak);
+        previous = Some(day);
+    }
+    longest
+}
@@ -0,0 +1,244 @@
+use ratatui::style::Color;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Colors used to draw the UI, resolved from the `theme` section of `config.yaml`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub text: Color,
+    pub highlight_bg: Color,
+    pub highlight_fg: Color,
+    pub border: Color,
+    pub title: Color,
+    pub footer_text: Color,
+    pub error_border: Color,
+    pub error_text: Color,
+    /// Start of the progress bar gradient; named and indexed colors are drawn flat.
+    pub progress: Color,
+    pub progress_empty: Color,
+}
+
+/// Brightness of the last filled progress bar cell relative to the first.
+const PROGRESS_MIN_BRIGHTNESS: f32 = 0.78;
+/// Brightness of the faintest activity calendar day relative to the busiest.
+const ACTIVITY_MIN_BRIGHTNESS: f32 = 0.45;
+
+impl Theme {
+    pub const DARK: Theme = Theme {
+        text: Color::Blue,
+        highlight_bg: Color::Blue,
+        highlight_fg: Color::Gray,
+        border: Color::Blue,
+        title: Color::LightYellow,
+        footer_text: Color::DarkGray,
+        error_border: Color::Red,
+        error_text: Color::Red,
+        progress: Color::Rgb(90, 145, 220),
+        progress_empty: Color::DarkGray,
+    };
+
+    pub const LIGHT: Theme = Theme {
+        text: Color::Rgb(24, 64, 140),
+        highlight_bg: Color::Rgb(24, 64, 140),
+        highlight_fg: Color::White,
+        border: Color::Rgb(24, 64, 140),
+        title: Color::Rgb(150, 60, 0),
+        footer_text: Color::Rgb(100, 100, 100),
+        error_border: Color::Rgb(190, 0, 0),
+        error_text: Color::Rgb(190, 0, 0),
+        progress: Color::Rgb(40, 100, 190),
+        progress_empty: Color::Rgb(200, 200, 200),
+    };
+
+    pub const SOLARIZED: Theme = Theme {
+        text: Color::Rgb(131, 148, 150),
+        highlight_bg: Color::Rgb(38, 139, 210),
+        highlight_fg: Color::Rgb(253, 246, 227),
+        border: Color::Rgb(88, 110, 117),
+        title: Color::Rgb(181, 137, 0),
+        footer_text: Color::Rgb(88, 110, 117),
+        error_border: Color::Rgb(220, 50, 47),
+        error_text: Color::Rgb(220, 50, 47),
+        progress: Color::Rgb(42, 161, 152),
+        progress_empty: Color::Rgb(7, 54, 66),
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        text: Color::White,
+        highlight_bg: Color::Yellow,
+        highlight_fg: Color::Black,
+        border: Color::White,
+        title: Color::Yellow,
+        footer_text: Color::White,
+        error_border: Color::LightRed,
+        error_text: Color::LightRed,
+        progress: Color::LightGreen,
+        progress_empty: Color::DarkGray,
+    };
+
+    /// Color of filled progress bar cell `index` out of `filled_cells`, darkening towards the end.
+    pub fn progress_color(&self, index: usize, filled_cells: usize) -> Color {
+        let Color::Rgb(red, green, blue) = self.progress else {
+            return self.progress;
+        };
+
+        let brightness = if filled_cells <= 1 {
+            1.0
+        } else {
+            let step = index as f32 / (filled_cells - 1) as f32;
+            1.0 - (1.0 - PROGRESS_MIN_BRIGHTNESS) * step
+        };
+
+        Color::Rgb(
+            scale_channel(red, brightness),
+            scale_channel(green, brightness),
+            scale_channel(blue, brightness),
+        )
+    }
+
+    /// Color of an activity calendar day at intensity `level` from 0 (nothing watched) to 4.
+    pub fn activity_color(&self, level: u8) -> Color {
+        if level == 0 {
+            return self.progress_empty;
+        }
+        let Color::Rgb(red, green, blue) = self.progress else {
+            return self.progress;
+        };
+
+        let brightness = ACTIVITY_MIN_BRIGHTNESS
+            + (1.0 - ACTIVITY_MIN_BRIGHTNESS) * (level.min(4) - 1) as f32 / 3.0;
+        Color::Rgb(
+            scale_channel(red, brightness),
+            scale_channel(green, brightness),
+            scale_channel(blue, brightness),
+        )
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DARK
+    }
+}
+
+fn scale_channel(channel: u8, brightness: f32) -> u8 {
+    ((channel as f32 * brightness).round()).clamp(0.0, 255.0) as u8
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    Solarized,
+    #[serde(alias = "high_contrast")]
+    HighContrast,
+}
+
+/// A color written as a name (`light-blue`), a 256-color index (`33`) or hex RGB (`#268bd2`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThemeColor(pub Color);
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ThemeColorVisitor)
+    }
+}
+
+struct ThemeColorVisitor;
+
+impl<'de> Visitor<'de> for ThemeColorVisitor {
+    type Value = ThemeColor;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a color name, a 0-255 index or #rrggbb")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        u8::try_from(value)
+            .map(|index| ThemeColor(Color::Indexed(index)))
+            .map_err(|_| E::custom(format!("color index {} is out of range 0-255", value)))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        u64::try_from(value)
+            .map_err(|_| E::custom(format!("color index {} is out of range 0-255", value)))
+            .and_then(|value| self.visit_u64(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Color::from_str(value.trim()).map(ThemeColor).map_err(|_| {
+            E::custom(format!(
+                "invalid color '{}', expected a name, a 0-255 index or #rrggbb",
+                value
+            ))
+        })
+    }
+}
+
+impl Serialize for ThemeColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl fmt::Display for ThemeColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The `theme` section: a built-in theme plus optional per-element overrides.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub name: ThemeName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_bg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub footer_text: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_border: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_text: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_empty: Option<ThemeColor>,
+}
+
+impl ThemeConfig {
+    pub fn resolve(&self) -> Theme {
+        let base = match self.name {
+            ThemeName::Dark => Theme::DARK,
+            ThemeName::Light => Theme::LIGHT,
+            ThemeName::Solarized => Theme::SOLARIZED,
+            ThemeName::HighContrast => Theme::HIGH_CONTRAST,
+        };
+        let pick = |color: Option<ThemeColor>, fallback: Color| color.map_or(fallback, |c| c.0);
+
+        Theme {
+            text: pick(self.text, base.text),
+            highlight_bg: pick(self.highlight_bg, base.highlight_bg),
+            highlight_fg: pick(self.highlight_fg, base.highlight_fg),
+            border: pick(self.border, base.border),
+            title: pick(self.title, base.title),
+            footer_text: pick(self.footer_text, base.footer_text),
+            error_border: pick(self.error_border, base.error_border),
+            error_text: pick(self.error_text, base.error_text),
+            progress: pick(self.progress, base.progress),
+            progress_empty: pick(self.progress_empty, base.progress_empty),
+        }
+    }
+}
@@ -1,38 +1,57 @@
-use crate::{app::App, app::InputMode, consts, Entry, Status};
+use crate::keymap::{KeyAction, KeyContext, Keymap};
+use crate::sort::SortMode;
+use crate::stats::{self, Stats};
+use crate::theme::Theme;
+use crate::{
+    app::App, app::InputMode, consts, database, format_minutes, fuzzy, scroll, Entry, Status,
+};
+use chrono::{Datelike, Days};
 use ratatui::{prelude::*, widgets::*};
 
-const EPISODE_PROGRESS_BASE_COLOR: (u8, u8, u8) = (90, 145, 220);
-const EPISODE_PROGRESS_MIN_BRIGHTNESS: f32 = 0.78;
-
 pub fn draw_ui(f: &mut Frame, app: &mut App) {
+    let size = f.size();
+    if size.width < consts::MIN_TERMINAL_WIDTH || size.height < consts::MIN_TERMINAL_HEIGHT {
+        // Nothing is drawn, so nothing can be clicked.
+        app.layout.clear();
+        app.column_layout.clear();
+        app.tab_layout.clear();
+        app.dropped_column_layout.clear();
+        draw_too_small(f, app);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(match app.input_mode {
-            InputMode::Normal
-            | InputMode::MaxEpisodes
-            | InputMode::ConfirmDelete
-            | InputMode::Dropped
-            | InputMode::ConfirmDeleteAllDropped
-            | InputMode::TotalEpisodes => [Constraint::Min(0), Constraint::Length(1)].as_ref(),
-            InputMode::Adding | InputMode::Editing => [
+        .constraints(if app.input_mode.uses_input_bar() {
+            [
                 Constraint::Min(0),
                 Constraint::Length(3),
                 Constraint::Length(1),
             ]
-            .as_ref(),
+            .as_ref()
+        } else {
+            [Constraint::Min(0), Constraint::Length(1)].as_ref()
         })
         .split(f.size());
 
     app.layout = chunks.to_vec();
 
     draw_main(f, chunks[0], app);
-    if let InputMode::Adding | InputMode::Editing = app.input_mode {
+    if app.input_mode.uses_input_bar() {
         draw_input(f, chunks[1], app);
     }
-    draw_footer(f, chunks[chunks.len() - 1]);
+    draw_footer(f, chunks[chunks.len() - 1], app);
+
+    if app.show_stats {
+        draw_stats_popup(f, app);
+    }
+
+    if app.input_mode == InputMode::Activity {
+        draw_activity_popup(f, app);
+    }
 
     if app.show_help {
-        draw_help(f, &app.config.storage_path);
+        draw_help(f, app);
     }
 
     if app.show_dropped {
@@ -43,36 +62,68 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         draw_total_episodes_popup(f, app);
     }
 
+    if app.input_mode == InputMode::ConfirmImport {
+        draw_import_popup(f, app);
+    }
+
     draw_title_popup(f, app);
     draw_error_popup(f, app);
+    draw_info_popup(f, app);
 
     if let InputMode::ConfirmDelete | InputMode::ConfirmDeleteAllDropped = app.input_mode {
         draw_confirmation_popup(f, app);
     }
 }
 
+fn draw_too_small(f: &mut Frame, app: &App) {
+    let size = f.size();
+    let text = format!(
+        "Terminal too small\n{}x{}, need {}x{}",
+        size.width,
+        size.height,
+        consts::MIN_TERMINAL_WIDTH,
+        consts::MIN_TERMINAL_HEIGHT
+    );
+    let area = popup_rect(size.width, 2, size);
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(app.theme.text))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
 fn draw_main(f: &mut Frame, area: Rect, app: &mut App) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(consts::COLUMN_1_WIDTH),
-            Constraint::Percentage(consts::COLUMN_2_WIDTH),
-            Constraint::Percentage(consts::COLUMN_3_WIDTH),
-        ])
-        .split(area);
+    let area = if app.show_details {
+        let (board, details) = split_detail_pane(area);
+        draw_detail_pane(f, details, app);
+        board
+    } else {
+        area
+    };
 
-    app.column_layout = chunks.to_vec();
+    let chunks = if area.width < consts::TABBED_BOARD_WIDTH {
+        draw_tabbed_board_layout(f, area, app)
+    } else {
+        app.tab_layout.clear();
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(consts::COLUMN_1_WIDTH),
+                Constraint::Percentage(consts::COLUMN_2_WIDTH),
+                Constraint::Percentage(consts::COLUMN_3_WIDTH),
+            ])
+            .split(area)
+            .to_vec()
+    };
 
-    for (i, status) in [Status::Planning, Status::Watching, Status::Completed]
-        .iter()
-        .enumerate()
-    {
-        let entry_in_status: Vec<(usize, &crate::Entry)> = app
-            .entry
-            .iter()
-            .enumerate()
-            .filter(|(_, s)| &s.status == status)
-            .collect();
+    app.column_layout = chunks.clone();
+
+    for (i, status) in Status::BOARD.iter().enumerate() {
+        if chunks[i].is_empty() {
+            continue;
+        }
+        let entry_in_status = app.get_entries_by_status(*status);
+        let (title_query, _) = fuzzy::split_tag_terms(&app.filter);
 
         let items: Vec<ListItem> = entry_in_status
             .iter()
@@ -81,11 +132,12 @@ fn draw_main(f: &mut Frame, area: Rect, app: &mut App) {
                 let suffix = format!(" (S{} E{})", s.season, s.episode);
                 let suffix_len = suffix.chars().count();
 
+                let marker = selection_marker(app, s);
                 let mut prefix = String::new();
                 if s.status == Status::Planning && (s.season != 1 || s.episode != 0) {
                     prefix.push_str("[P] ");
                 }
-                let prefix_len = prefix.chars().count();
+                let prefix_len = prefix.chars().count() + marker.width();
 
                 let max_title_chars = if col_width > suffix_len + prefix_len + consts::PADDING {
                     col_width - suffix_len - prefix_len - consts::PADDING
@@ -93,76 +145,456 @@ fn draw_main(f: &mut Frame, area: Rect, app: &mut App) {
                     0
                 };
 
-                let title = if s.title.chars().count() > max_title_chars {
+                let (title, ellipsis) = if s.title.chars().count() > max_title_chars {
                     let take = max_title_chars.saturating_sub(3);
-                    let mut truncated_title = s.title.chars().take(take).collect::<String>();
-                    truncated_title.push_str("...");
-                    truncated_title
+                    (s.title.chars().take(take).collect::<String>(), "...")
                 } else {
-                    s.title.clone()
+                    (s.title.clone(), "")
                 };
-                ListItem::new(format!("{}{}{}", prefix, title, suffix))
-                    .style(Style::default().fg(consts::TEXT_COLOR))
+                let matched = fuzzy::fuzzy_match(&title_query, &s.title).unwrap_or_default();
+                let mut spans = vec![marker, Span::raw(prefix)];
+                spans.extend(highlight_matches(&title, &matched, app.theme.title));
+                spans.push(Span::raw(format!("{}{}", ellipsis, suffix)));
+                ListItem::new(Line::from(spans)).style(Style::default().fg(app.theme.text))
             })
             .collect();
 
+        let title = column_title(app, *status, entry_in_status.len());
+        let len = entry_in_status.len();
+        let selected = entry_in_status
+            .iter()
+            .position(|(idx, _)| *idx == app.selected_index);
         let list = List::new(items)
             .block(
                 Block::default()
-                    .title(format!(
-                        "{} ({})",
-                        status.to_string(),
-                        entry_in_status.len()
-                    ))
+                    .title(title)
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(consts::BORDER_COLOR))
-                    .title_style(Style::default().fg(consts::TITLE_COLOR)),
+                    .border_style(Style::default().fg(app.theme.border))
+                    .title_style(Style::default().fg(app.theme.title)),
             )
             .highlight_style(
                 Style::default()
                     .add_modifier(Modifier::BOLD)
-                    .bg(consts::HIGHLIGHT_BG)
-                    .fg(consts::HIGHLIGHT_FG),
+                    .bg(app.theme.highlight_bg)
+                    .fg(app.theme.highlight_fg),
             );
 
-        let mut state = ListState::default();
-        if let Some(selected_in_status) = entry_in_status
+        let rows = chunks[i].height.saturating_sub(2) as usize;
+        let previous = app.column_offsets.get(status).copied().unwrap_or(0);
+        let offset = scroll::offset_for(previous, selected, len, rows);
+        app.column_offsets.insert(*status, offset);
+
+        let mut state = ListState::default()
+            .with_offset(offset)
+            .with_selected(selected);
+        f.render_stateful_widget(list, chunks[i], &mut state);
+        // On the right border, between the corners.
+        let track = chunks[i].inner(&Margin::new(0, 1));
+        draw_scrollbar(f, track, &app.theme, len, offset, rows);
+    }
+}
+
+/// A scrollbar along the right edge of `area` while a list of `len` rows is taller than the
+/// `rows` it is shown in.
+fn draw_scrollbar(
+    f: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    len: usize,
+    offset: usize,
+    rows: usize,
+) {
+    if len <= rows || area.is_empty() {
+        return;
+    }
+    let mut state = ScrollbarState::new(len - rows + 1)
+        .position(offset)
+        .viewport_content_length(rows);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_style(Style::default().fg(theme.border))
+        .thumb_style(Style::default().fg(theme.title));
+    f.render_stateful_widget(scrollbar, area, &mut state);
+}
+
+/// Draws one tab per column above `area` and returns the column areas, which are empty for
+/// all but the column of the selected entry.
+fn draw_tabbed_board_layout(f: &mut Frame, area: Rect, app: &mut App) -> Vec<Rect> {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+    let (tabs_area, column_area) = (chunks[0], chunks[1]);
+
+    let shown = app
+        .entry
+        .get(app.selected_index)
+        .map(|e| e.status)
+        .filter(|status| *status != Status::Dropped)
+        .unwrap_or(Status::Planning);
+
+    let counts: Vec<usize> = Status::BOARD
+        .iter()
+        .map(|status| app.get_entries_by_status(*status).len())
+        .collect();
+    let mut labels: Vec<String> = Status::BOARD
+        .iter()
+        .zip(&counts)
+        .map(|(status, count)| format!(" {} ({}) ", status, count))
+        .collect();
+    let separators = Status::BOARD.len() as u16 - 1;
+    if labels.iter().map(|l| text_width(l)).sum::<u16>() + separators > tabs_area.width {
+        labels = Status::BOARD
             .iter()
-            .position(|(idx, _)| *idx == app.selected_index)
-        {
-            state.select(Some(selected_in_status));
+            .zip(&counts)
+            .map(|(status, count)| format!(" {:.1} {} ", status.to_string(), count))
+            .collect();
+    }
+
+    let mut spans = Vec::new();
+    app.tab_layout.clear();
+    let mut x = tabs_area.x;
+    for (status, label) in Status::BOARD.iter().zip(labels) {
+        if !spans.is_empty() {
+            spans.push(Span::styled("│", Style::default().fg(app.theme.border)));
+            x += 1;
         }
+        let width = text_width(&label).min((tabs_area.x + tabs_area.width).saturating_sub(x));
+        app.tab_layout.push(Rect {
+            x,
+            y: tabs_area.y,
+            width,
+            height: 1,
+        });
+        x += width;
+        let style = if *status == shown {
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .bg(app.theme.highlight_bg)
+                .fg(app.theme.highlight_fg)
+        } else {
+            Style::default().fg(app.theme.title)
+        };
+        spans.push(Span::styled(label, style));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), tabs_area);
 
-        f.render_stateful_widget(list, chunks[i], &mut state);
+    Status::BOARD
+        .iter()
+        .map(|status| {
+            if *status == shown {
+                column_area
+            } else {
+                Rect {
+                    width: 0,
+                    height: 0,
+                    ..column_area
+                }
+            }
+        })
+        .collect()
+}
+
+/// Splits off room for the detail pane: beside the board on wide terminals, below it
+/// otherwise. Returns the board area first.
+fn split_detail_pane(area: Rect) -> (Rect, Rect) {
+    let (direction, size) = if area.width >= consts::DETAIL_PANE_SIDE_MIN_WIDTH {
+        (Direction::Horizontal, consts::DETAIL_PANE_WIDTH)
+    } else {
+        (
+            Direction::Vertical,
+            consts::DETAIL_PANE_HEIGHT.min(area.height / 2),
+        )
+    };
+    let chunks = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Min(0), Constraint::Length(size)])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+fn draw_detail_pane(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title("Details")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(entry) = app.entry.get(app.selected_index) else {
+        let empty = Paragraph::new("No entry selected").style(Style::default().fg(app.theme.text));
+        f.render_widget(empty, inner);
+        return;
+    };
+
+    let label = Style::default().fg(app.theme.footer_text);
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", name), label),
+            Span::raw(value),
+        ])
+    };
+
+    // Below the board the pane is wide but short, so the optional fields go on the right.
+    let (main_area, extra_area) = if inner.width >= consts::DETAIL_PANE_WIDTH * 2 {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (inner, None)
+    };
+
+    let progress = app.selected_entry_progress();
+    let episodes = match entry.episodes_remaining() {
+        Some(remaining) if entry.max_episodes > 0 => format!(
+            "{}/{} watched, {} left",
+            entry.watched_episodes, entry.max_episodes, remaining
+        ),
+        _ => format!("{} watched", entry.watched_episodes),
+    };
+
+    let mut lines = vec![
+        Line::styled(
+            entry.title.clone(),
+            Style::default()
+                .fg(app.theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        field("Status", entry.status.to_string()),
+        field("Position", format!("S{} E{}", entry.season, entry.episode)),
+        field("Episodes", episodes),
+        field("Progress", format!("{}%", progress)),
+        progress_bar_line(&app.theme, progress, main_area.width),
+    ];
+
+    let mut extra = Vec::new();
+    if entry.runtime_minutes > 0 || !entry.season_runtimes.is_empty() {
+        extra.push(field(
+            "Runtime",
+            format!("{} min", entry.runtime_for_season(entry.season)),
+        ));
+        extra.push(field("Watched", format_minutes(entry.minutes_watched())));
+        if let Some(minutes) = entry.minutes_remaining() {
+            extra.push(field("Left", format_minutes(minutes)));
+        }
+    }
+    if let Some(date) = entry.next_air_date {
+        extra.push(field("Next air", date.to_string()));
+    }
+    let now = database::now();
+    if let Some(next) = entry.watch_sessions.iter().find(|s| **s >= now) {
+        extra.push(field("Session", next.format("%Y-%m-%d %H:%M").to_string()));
+    }
+    if !entry.tags.is_empty() {
+        extra.push(field("Tags", entry.tags.join(", ")));
+    }
+    if let Some(updated_at) = entry.updated_at {
+        extra.push(field(
+            "Updated",
+            updated_at.format("%Y-%m-%d %H:%M").to_string(),
+        ));
+    }
+
+    let text_style = Style::default().fg(app.theme.text);
+    match extra_area {
+        Some(extra_area) => {
+            let extra = Paragraph::new(extra)
+                .style(text_style)
+                .wrap(Wrap { trim: false });
+            f.render_widget(extra, extra_area);
+        }
+        None if !extra.is_empty() => {
+            lines.push(Line::raw(""));
+            lines.append(&mut extra);
+        }
+        None => {}
+    }
+    let paragraph = Paragraph::new(lines)
+        .style(text_style)
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, main_area);
+}
+
+/// A dot in front of entries selected for bulk actions, and room for it in front of the
+/// others while anything is selected.
+fn selection_marker(app: &App, entry: &Entry) -> Span<'static> {
+    if app.selection.is_empty() {
+        Span::raw("")
+    } else if app.selection.contains(&entry.id) {
+        Span::styled("● ", Style::default().fg(app.theme.title))
+    } else {
+        Span::raw("  ")
+    }
+}
+
+/// "Watching (3)", or "Watching (1/3)" while a filter hides some entries, followed by the
+/// sort mode unless the column is sorted manually.
+fn column_title(app: &App, status: Status, shown: usize) -> String {
+    let mut title = if app.filter.trim().is_empty() {
+        format!("{} ({})", status, shown)
+    } else {
+        let total = app.entry.iter().filter(|e| e.status == status).count();
+        format!("{} ({}/{})", status, shown, total)
+    };
+    let mode = app.sort_mode(status);
+    if mode != SortMode::Manual {
+        title.push_str(&format!(" by {}", mode));
     }
+    title
+}
+
+/// Splits `text` into spans, drawing the chars at `matched` positions in `color`.
+fn highlight_matches(text: &str, matched: &[usize], color: Color) -> Vec<Span<'static>> {
+    let style = Style::default()
+        .fg(color)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut runs: Vec<(String, bool)> = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        let is_match = matched.binary_search(&i).is_ok();
+        match runs.last_mut() {
+            Some((run, run_matched)) if *run_matched == is_match => run.push(c),
+            _ => runs.push((c.to_string(), is_match)),
+        }
+    }
+    runs.into_iter()
+        .map(|(run, is_match)| {
+            if is_match {
+                Span::styled(run, style)
+            } else {
+                Span::raw(run)
+            }
+        })
+        .collect()
 }
 
 fn draw_input(f: &mut Frame, area: Rect, app: &mut App) {
     let title = match app.input_mode {
         InputMode::Adding => "New Entry",
         InputMode::Editing => "Edit Entry",
+        InputMode::ExportPath => "Export to (.json/.csv/.xml/.md/.html/.ics)",
+        InputMode::ImportPath => "Import from (.json/.csv/.xml)",
+        InputMode::Command => "Command (tab: complete, up/down: history)",
+        InputMode::Filter => "Filter (enter: keep, esc: clear)",
         _ => "",
     };
-    let input = Paragraph::new(app.input.as_str())
-        .style(Style::default().fg(consts::TEXT_COLOR))
+    let title = match app.input_mode {
+        InputMode::Command if !app.completions.is_empty() => command_completions_title(app),
+        _ => title.to_string(),
+    };
+    // The command palette shows its `:` prompt in front of the typed text.
+    let prompt = if app.input_mode == InputMode::Command {
+        ":"
+    } else {
+        ""
+    };
+    let input = Paragraph::new(format!("{}{}", prompt, app.input))
+        .style(Style::default().fg(app.theme.text))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(Style::default().fg(consts::BORDER_COLOR))
-                .title_style(Style::default().fg(consts::TITLE_COLOR)),
+                .border_style(Style::default().fg(app.theme.border))
+                .title_style(Style::default().fg(app.theme.title)),
         );
     f.render_widget(input, area);
-    f.set_cursor(area.x + app.cursor_position as u16 + 1, area.y + 1);
+    f.set_cursor(
+        area.x + (prompt.len() + app.cursor_position) as u16 + 1,
+        area.y + 1,
+    );
 }
 
-fn draw_footer(f: &mut Frame, area: Rect) {
-    let text = "q: quit | a: add | e: edit | h: help | d: dropped | up/down: select | left/right: navigate";
-    let text_width = text.len() as u16;
-    let text = if text_width > area.width {
+/// The last words of the completion candidates, the current one in brackets.
+fn command_completions_title(app: &App) -> String {
+    app.completions
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| {
+            let command = candidate.split_whitespace().next().unwrap_or_default();
+            let word = match candidate.split_once(char::is_whitespace) {
+                // Titles complete as a whole, so show everything after the command.
+                Some((_, rest)) if matches!(command, "goto" | "rename") => rest,
+                _ => candidate
+                    .rsplit(char::is_whitespace)
+                    .next()
+                    .unwrap_or_default(),
+            };
+            if app.completion_index == Some(i) {
+                format!("[{}]", word)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn draw_footer(f: &mut Frame, area: Rect, app: &App) {
+    let totals = get_time_totals_text(app);
+    let totals_width = totals.as_ref().map_or(0, |t| t.chars().count() as u16 + 2);
+    let (area, totals_area) = if totals_width > 0 && area.width > totals_width * 2 {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(totals_width)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
+
+    let pending = app.pending_keys_text().or_else(|| {
+        let mut status = Vec::new();
+        if !app.filter.trim().is_empty() && app.input_mode != InputMode::Filter {
+            status.push(format!("/{}", app.filter));
+        }
+        let picked = app.selected_entries().len();
+        if picked > 0 && !app.show_dropped {
+            status.push(format!("{} selected", picked));
+        }
+        (!status.is_empty()).then(|| status.join(" "))
+    });
+    let pending_width = pending.as_ref().map_or(0, |p| p.chars().count() as u16 + 2);
+    let (pending_area, area) = if pending_width > 0 && area.width > pending_width * 2 {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(pending_width), Constraint::Min(0)])
+            .split(area);
+        (Some(chunks[0]), chunks[1])
+    } else {
+        (None, area)
+    };
+
+    let text = key_hints(
+        app,
+        &[
+            (&[KeyAction::Quit], "quit"),
+            (&[KeyAction::Add], "add"),
+            (&[KeyAction::Edit], "edit"),
+            (&[KeyAction::Help], "help"),
+            (&[KeyAction::Dropped], "dropped"),
+            (&[KeyAction::Up, KeyAction::Down], "select"),
+            (&[KeyAction::Left, KeyAction::Right], "navigate"),
+        ],
+        " | ",
+    );
+    // Narrow terminals get the keys that lead to everything else.
+    let text = if text.chars().count() as u16 > area.width {
+        key_hints(
+            app,
+            &[(&[KeyAction::Help], "help"), (&[KeyAction::Quit], "quit")],
+            " | ",
+        )
+    } else {
+        text
+    };
+    let text = if text.chars().count() as u16 > area.width {
         let mut truncated_text = text
             .chars()
-            .take(area.width as usize - 3)
+            .take((area.width as usize).saturating_sub(3))
             .collect::<String>();
         truncated_text.push_str("...");
         truncated_text
@@ -171,251 +603,683 @@ fn draw_footer(f: &mut Frame, area: Rect) {
     };
 
     let paragraph = Paragraph::new(text)
-        .style(Style::default().fg(consts::FOOTER_TEXT_COLOR))
+        .style(Style::default().fg(app.theme.footer_text))
         .alignment(Alignment::Center);
     f.render_widget(paragraph, area);
+
+    if let (Some(pending), Some(pending_area)) = (pending, pending_area) {
+        let pending_p = Paragraph::new(pending)
+            .style(Style::default().fg(app.theme.title))
+            .alignment(Alignment::Left);
+        f.render_widget(pending_p, pending_area);
+    }
+
+    if let (Some(totals), Some(totals_area)) = (totals, totals_area) {
+        let totals_p = Paragraph::new(totals)
+            .style(Style::default().fg(app.theme.footer_text))
+            .alignment(Alignment::Right);
+        f.render_widget(totals_p, totals_area);
+    }
 }
 
-fn draw_help(f: &mut Frame, storage_path: &str) {
-    let area = centered_rect(
-        consts::HELP_POPUP_WIDTH,
-        consts::HELP_POPUP_HEIGHT,
-        f.size(),
-    );
+/// Board-wide watch time summary, or `None` while no entry has a runtime set.
+fn get_time_totals_text(app: &App) -> Option<String> {
+    let watched = app.total_minutes_watched();
+    let watching = app.total_minutes_remaining(Status::Watching);
+    let planning = app.total_minutes_remaining(Status::Planning);
+
+    if watched == 0 && watching == 0 && planning == 0 {
+        return None;
+    }
+
+    Some(format!(
+        "watched {} | watching {} left | planning {}",
+        format_minutes(watched),
+        format_minutes(watching),
+        format_minutes(planning)
+    ))
+}
+
+fn draw_help(f: &mut Frame, app: &mut App) {
+    let left = get_help_text_left(&app.keymap);
+    let right = get_help_text_right(&app.keymap);
+    let storage = format!("Storage: {}", app.config.storage_path);
+
+    // Border, padding and margin around the text, plus the storage line.
+    let (chrome_x, chrome_y) = (8, 7);
+    let left_width = text_width(&left);
+    let right_width = text_width(&right);
+    let column_width = left_width.max(right_width);
+    let two_columns = column_width * 2 + 2 + chrome_x <= f.size().width;
+    let (width, rows) = if two_columns {
+        let rows = left.lines().count().max(right.lines().count());
+        (column_width * 2 + 2, rows as u16)
+    } else {
+        let rows = left.lines().count() + right.lines().count();
+        (column_width, rows as u16)
+    };
+    let width = width.max(text_width(&storage)) + chrome_x;
+    let area = popup_rect(width, rows + chrome_y, f.size());
+
+    let body_height = area.height.saturating_sub(chrome_y);
+    let body_width = area.width.saturating_sub(chrome_x);
+    let rows = if two_columns {
+        wrapped_rows(&left, body_width / 2).max(wrapped_rows(&right, body_width / 2))
+    } else {
+        wrapped_rows(&left, body_width) + wrapped_rows(&right, body_width)
+    };
+    let max_scroll = rows.saturating_sub(body_height);
+    app.help_scroll = app.help_scroll.min(max_scroll);
+
     let block = Block::default()
-        .title("Help")
+        .title(if max_scroll > 0 {
+            "Help (up/down: scroll)"
+        } else {
+            "Help"
+        })
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(consts::BORDER_COLOR))
-        .title_style(Style::default().fg(consts::TITLE_COLOR))
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title))
         .padding(Padding::new(2, 2, 1, 1));
 
     f.render_widget(Clear, area);
     f.render_widget(block.clone(), area);
 
-    let is_small = area.width < 60;
-
     let chunks = Layout::default()
         .margin(1)
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
         .split(block.inner(area));
 
-    if is_small {
-        let help_text = format!("{}\n{}", get_help_text_left(), get_help_text_right());
-        let help_p = Paragraph::new(help_text)
-            .style(Style::default().fg(consts::TEXT_COLOR))
-            .wrap(Wrap { trim: true });
-        f.render_widget(help_p, chunks[0]);
-    } else {
+    let paragraph = |text: String| {
+        Paragraph::new(text)
+            .style(Style::default().fg(app.theme.text))
+            .wrap(Wrap { trim: true })
+            .scroll((app.help_scroll, 0))
+    };
+    if two_columns {
         let help_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
             .split(chunks[0]);
 
-        let left_p = Paragraph::new(get_help_text_left())
-            .style(Style::default().fg(consts::TEXT_COLOR))
-            .wrap(Wrap { trim: true });
-        let right_p = Paragraph::new(get_help_text_right())
-            .style(Style::default().fg(consts::TEXT_COLOR))
-            .wrap(Wrap { trim: true });
-        f.render_widget(left_p, help_chunks[0]);
-        f.render_widget(right_p, help_chunks[1]);
+        f.render_widget(paragraph(left), help_chunks[0]);
+        f.render_widget(paragraph(right), help_chunks[1]);
+    } else {
+        f.render_widget(paragraph(format!("{}\n{}", left, right)), chunks[0]);
     }
 
-    let storage_p = Paragraph::new(format!("Storage: {}", storage_path))
-        .style(Style::default().fg(consts::TEXT_COLOR))
+    let storage_p = Paragraph::new(storage)
+        .style(Style::default().fg(app.theme.text))
         .alignment(Alignment::Center);
 
     f.render_widget(storage_p, chunks[1]);
 }
 
-fn get_help_text_left() -> String {
-    "a: add new entry
-    e: edit entry
-    d: show dropped
-    o: total episodes
-
-    +: increase episode
-    -: decrease episode
-
-    up/down: select row
-    Shift + navigation: move entry
-
-    h: toggle help
-    "
-    .to_string()
+fn get_help_text_left(keymap: &Keymap) -> String {
+    let lines = keymap.help_lines(KeyContext::Normal);
+    let (left, _) = lines.split_at(lines.len().div_ceil(2));
+    left.join("\n")
 }
 
-fn get_help_text_right() -> String {
-    "(esc: abort)
-
-
-    #: increase season
-    x: remove entry
+fn get_help_text_right(keymap: &Keymap) -> String {
+    let lines = keymap.help_lines(KeyContext::Normal);
+    let (_, right) = lines.split_at(lines.len().div_ceil(2));
+    format!(
+        "{}\n\nmouse: drag & drop\nesc: abort input",
+        right.join("\n")
+    )
+}
 
-    left/right: select column
-    mouse: drag & drop
+/// `keys: label` hints for footers, following the active keymap.
+fn key_hints(app: &App, hints: &[(&[KeyAction], &str)], separator: &str) -> String {
+    hints
+        .iter()
+        .map(|(actions, label)| {
+            let keys: Vec<String> = actions.iter().map(|a| app.keymap.describe(*a)).collect();
+            format!("{}: {}", keys.join("/"), label)
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
 
-    q: quit
-    "
-    .to_string()
+/// A `width` x `height` rect centered in `r`, shrunk to fit inside it.
+fn popup_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
+/// Width of the longest line of `text`, in chars.
+fn text_width(text: &str) -> u16 {
+    text.lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as u16
+}
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+/// Rows `text` takes when wrapped at `width`, estimated by chars like the message popups.
+fn wrapped_rows(text: &str, width: u16) -> u16 {
+    if width == 0 {
+        return text.lines().count() as u16;
+    }
+    text.lines()
+        .map(|line| (line.chars().count() as u16).div_ceil(width).max(1))
+        .sum()
 }
 
 fn draw_total_episodes_popup(f: &mut Frame, app: &mut App) {
-    let area = centered_rect(42, 32, f.size());
+    // Seven fields, the progress or input rows, help, and the border, padding and margin.
+    let area = popup_rect(consts::EPISODES_POPUP_WIDTH, 7 + 7 + 6, f.size());
 
     let block = Block::default()
         .title("Episodes Watched")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(consts::BORDER_COLOR))
-        .title_style(Style::default().fg(consts::TITLE_COLOR))
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title))
         .padding(Padding::new(1, 1, 1, 1));
 
     f.render_widget(Clear, area);
     f.render_widget(block.clone(), area);
 
-    let constraints = if app.input_mode == InputMode::MaxEpisodes {
-        vec![
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Length(5),
-            Constraint::Length(1),
-        ]
+    let input_title = match app.input_mode {
+        InputMode::MaxEpisodes => Some("Set Max Episodes"),
+        InputMode::Runtime => Some("Set Runtime (min)"),
+        InputMode::SeasonRuntime => Some("Set Season Runtime (min)"),
+        InputMode::AirDate => Some("Next Air Date (YYYY-MM-DD)"),
+        InputMode::WatchSession => Some("Plan Session (YYYY-MM-DD HH:MM)"),
+        _ => None,
+    };
+
+    let mut constraints = vec![Constraint::Length(1); 7];
+    if input_title.is_some() {
+        constraints.extend([Constraint::Length(5), Constraint::Length(1)]);
     } else {
-        vec![
-            Constraint::Length(1),
-            Constraint::Length(1),
+        constraints.extend([
             Constraint::Length(3),
             Constraint::Length(3),
             Constraint::Length(1),
-        ]
-    };
+        ]);
+    }
     let chunks = Layout::default()
         .margin(1)
         .direction(Direction::Vertical)
         .constraints(constraints)
         .split(block.inner(area));
 
-    let watched_text = if let Some(entry) = app.entry.get(app.selected_index) {
+    let entry = app.entry.get(app.selected_index);
+
+    let watched_text = if let Some(entry) = entry {
         format!("Watched Episodes: {}", entry.watched_episodes)
     } else {
         "N/A".to_string()
     };
 
-    let watched_paragraph = Paragraph::new(watched_text)
-        .style(Style::default().fg(consts::TEXT_COLOR))
-        .alignment(Alignment::Center);
-    f.render_widget(watched_paragraph, chunks[0]);
-
-    let max_text = if let Some(entry) = app.entry.get(app.selected_index) {
+    let max_text = if let Some(entry) = entry {
         format!("Max Episodes: {}", entry.max_episodes)
     } else {
         "Max Episodes: N/A".to_string()
     };
-    let max_paragraph = Paragraph::new(max_text)
-        .style(Style::default().fg(consts::TEXT_COLOR))
-        .alignment(Alignment::Center);
-    f.render_widget(max_paragraph, chunks[1]);
 
-    let help_text = if app.input_mode == InputMode::MaxEpisodes {
-        "Max Episodes"
-    } else {
-        "(o/enter: close, +/-: adjust, #: set max)"
+    let runtime_text = match entry {
+        Some(entry) => {
+            let mut text = format!("Runtime: {} min", entry.runtime_minutes);
+            if let Some(season_runtime) = entry.season_runtimes.get(&entry.season) {
+                text.push_str(&format!(" (S{}: {} min)", entry.season, season_runtime));
+            }
+            text
+        }
+        None => "Runtime: N/A".to_string(),
+    };
+
+    let time_watched_text = match entry {
+        Some(entry) => format!("Time Watched: {}", format_minutes(entry.minutes_watched())),
+        None => "Time Watched: N/A".to_string(),
+    };
+
+    let time_remaining_text = match entry.and_then(Entry::minutes_remaining) {
+        Some(minutes) => format!("Time Remaining: {}", format_minutes(minutes)),
+        None => "Time Remaining: N/A".to_string(),
+    };
+
+    let air_date_text = match entry.and_then(|e| e.next_air_date) {
+        Some(date) => format!("Next Air Date: {}", date),
+        None => "Next Air Date: N/A".to_string(),
+    };
+
+    let sessions_text = match entry {
+        Some(entry) if !entry.watch_sessions.is_empty() => {
+            let now = database::now();
+            match entry.watch_sessions.iter().find(|s| **s >= now) {
+                Some(next) => format!(
+                    "Sessions: {} (next {})",
+                    entry.watch_sessions.len(),
+                    next.format("%Y-%m-%d %H:%M")
+                ),
+                None => format!("Sessions: {} (all past)", entry.watch_sessions.len()),
+            }
+        }
+        _ => "Sessions: none".to_string(),
+    };
+
+    for (i, text) in [
+        watched_text,
+        max_text,
+        runtime_text,
+        time_watched_text,
+        time_remaining_text,
+        air_date_text,
+        sessions_text,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let paragraph = Paragraph::new(text)
+            .style(Style::default().fg(app.theme.text))
+            .alignment(Alignment::Center);
+        f.render_widget(paragraph, chunks[i]);
+    }
+
+    let help_text = match app.input_mode {
+        InputMode::MaxEpisodes => "Max Episodes",
+        InputMode::Runtime => "Minutes per episode",
+        InputMode::SeasonRuntime => "Minutes per episode this season (0: clear)",
+        InputMode::AirDate => "Later episodes air weekly (empty: clear)",
+        InputMode::WatchSession => "Local time of the planned session",
+        _ => &format!(
+            "({})",
+            key_hints(
+                app,
+                &[
+                    (&[KeyAction::Episodes, KeyAction::Close], "close"),
+                    (&[KeyAction::WatchedUp, KeyAction::WatchedDown], "adjust"),
+                    (&[KeyAction::SetMaxEpisodes], "max"),
+                    (
+                        &[KeyAction::SetRuntime, KeyAction::SetSeasonRuntime],
+                        "runtime"
+                    ),
+                    (&[KeyAction::SetAirDate], "air date"),
+                    (
+                        &[KeyAction::PlanSession, KeyAction::ClearSessions],
+                        "sessions"
+                    ),
+                ],
+                ", ",
+            )
+        ),
     };
     let help_paragraph = Paragraph::new(help_text)
-        .style(Style::default().fg(consts::FOOTER_TEXT_COLOR))
-        .alignment(Alignment::Center);
+        .style(Style::default().fg(app.theme.footer_text))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
-    if app.input_mode == InputMode::MaxEpisodes {
-        draw_popup_input(f, chunks[2], app, "Set Max Episodes");
-        f.render_widget(help_paragraph, chunks[3]);
+    if let Some(input_title) = input_title {
+        draw_popup_input(f, chunks[7], app, input_title);
+        f.render_widget(help_paragraph, chunks[8]);
     } else {
         let progress_label =
             Paragraph::new(format!("Progress: {}%", app.selected_entry_progress()))
-                .style(Style::default().fg(consts::TEXT_COLOR))
+                .style(Style::default().fg(app.theme.text))
                 .alignment(Alignment::Center);
-        f.render_widget(progress_label, chunks[2]);
+        f.render_widget(progress_label, chunks[7]);
 
-        draw_episode_progress_bar(f, chunks[3], app.selected_entry_progress());
-        f.render_widget(help_paragraph, chunks[4]);
+        draw_episode_progress_bar(f, chunks[8], &app.theme, app.selected_entry_progress());
+        f.render_widget(help_paragraph, chunks[9]);
     }
 }
 
-fn draw_episode_progress_bar(f: &mut Frame, area: Rect, progress: u16) {
+fn draw_stats_popup(f: &mut Frame, app: &App) {
+    let stats = Stats::new(&app.entry, database::now().date());
+    let theme = &app.theme;
+
+    let mut sections = vec![
+        ("", 1),
+        ("Entries", stats.per_status.len() as u16),
+        ("Progress", 2),
+    ];
+    if !stats.longest_running.is_empty() {
+        sections.push(("Longest Running", stats.longest_running.len() as u16));
+    }
+    if !stats.episodes_per_week.is_empty() {
+        sections.push(("Episodes per Week", 6));
+    }
+    // Each titled section has a heading and a blank row above it; then help and the chrome.
+    let body: u16 = sections
+        .iter()
+        .map(|(title, rows)| rows + if title.is_empty() { 0 } else { 2 })
+        .sum();
+    let area = popup_rect(consts::STATS_POPUP_WIDTH, body + 2 + 4, f.size());
+
+    let block = Block::default()
+        .title("Statistics")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .title_style(Style::default().fg(theme.title))
+        .padding(Padding::new(1, 1, 1, 1));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block.clone(), area);
+
+    let mut constraints = Vec::new();
+    for (title, rows) in &sections {
+        if !title.is_empty() {
+            constraints.extend([Constraint::Length(1), Constraint::Length(1)]);
+        }
+        constraints.push(Constraint::Length(*rows));
+    }
+    constraints.extend([Constraint::Min(0), Constraint::Length(1)]);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(block.inner(area));
+
+    // Headings go above their sections; `areas` yields the body of each section in order.
+    let mut chunk = chunks.iter().copied();
+    let mut areas = Vec::new();
+    for (title, _) in &sections {
+        if !title.is_empty() {
+            chunk.next();
+            let heading = Paragraph::new(*title).style(Style::default().fg(theme.title));
+            f.render_widget(heading, chunk.next().unwrap_or_default());
+        }
+        areas.push(chunk.next().unwrap_or_default());
+    }
+    let mut areas = areas.into_iter();
+    let mut section = || areas.next().unwrap_or_default();
+    let text = Style::default().fg(theme.text);
+
+    let summary = format!(
+        "{} entries, {} episodes watched",
+        stats.entries, stats.episodes_watched
+    );
+    f.render_widget(
+        Paragraph::new(summary)
+            .style(text)
+            .alignment(Alignment::Center),
+        section(),
+    );
+
+    let bars: Vec<Bar> = stats
+        .per_status
+        .iter()
+        .map(|count| {
+            Bar::default()
+                .label(format!("{:<9}", count.status.to_string()).into())
+                .value(count.entries as u64)
+        })
+        .collect();
+    let status_chart = BarChart::default()
+        .direction(Direction::Horizontal)
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Style::default().fg(theme.progress))
+        .value_style(Style::default().fg(theme.highlight_fg).bg(theme.progress))
+        .label_style(text);
+    f.render_widget(status_chart, section());
+
+    let rates = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1)])
+        .split(section());
+    for (area, label, rate) in [
+        (rates[0], "Completion rate", stats.completion_rate),
+        (rates[1], "Watching progress", stats.watching_progress),
+    ] {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(25), Constraint::Min(0)])
+            .split(area);
+        let value = rate.map_or("n/a".to_string(), |rate| format!("{}%", rate));
+        let label = Paragraph::new(format!("{:<19}{:>4}", label, value)).style(text);
+        f.render_widget(label, columns[0]);
+        if let Some(rate) = rate {
+            draw_episode_progress_bar(f, columns[1], theme, rate);
+        }
+    }
+
+    if !stats.longest_running.is_empty() {
+        let lines: Vec<Line> = stats
+            .longest_running
+            .iter()
+            .map(|show| Line::from(format!("{:>5}  {}", show.episodes, show.title)))
+            .collect();
+        f.render_widget(Paragraph::new(lines).style(text), section());
+    }
+
+    if !stats.episodes_per_week.is_empty() {
+        // Bars are three cells wide with a gap; narrow popups show the latest weeks only.
+        let area = section();
+        let fits = (area.width as usize + 1) / 4;
+        let bars: Vec<Bar> = stats
+            .episodes_per_week
+            .iter()
+            .skip(stats.episodes_per_week.len().saturating_sub(fits))
+            .map(|week| {
+                Bar::default()
+                    .label(format!("W{:02}", week.week.iso_week().week()).into())
+                    .value(week.episodes as u64)
+            })
+            .collect();
+        let week_chart = BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(3)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(theme.progress))
+            .value_style(Style::default().fg(theme.highlight_fg).bg(theme.progress))
+            .label_style(text);
+        f.render_widget(week_chart, area);
+    }
+
+    let help = Paragraph::new(format!(
+        "({})",
+        key_hints(
+            app,
+            &[(&[KeyAction::Stats, KeyAction::ClearSelection], "close")],
+            ", "
+        )
+    ))
+    .style(Style::default().fg(theme.footer_text))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[chunks.len() - 1]);
+}
+
+/// Glyphs for the activity calendar intensity levels, so they read without colors too.
+const ACTIVITY_GLYPHS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+fn draw_activity_popup(f: &mut Frame, app: &App) {
+    let today = database::now().date();
+    let days = stats::episodes_per_day(&app.entry);
+    let busiest = days.values().copied().max().unwrap_or(0);
+    let theme = &app.theme;
+
+    // Weekday labels, one column per week, and the border and padding around them; below the
+    // grid the streaks, the picked day with its titles, and the help.
+    let label_width = 4;
+    let width = label_width + stats::ACTIVITY_WEEKS as u16 + 4;
+    let height = 1 + 7 + 2 + 2 + consts::ACTIVITY_DAY_ROWS as u16 + 1 + 4;
+    let area = popup_rect(width, height, f.size());
+
+    let block = Block::default()
+        .title("Activity")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .title_style(Style::default().fg(theme.title))
+        .padding(Padding::new(1, 1, 1, 1));
+
+    f.render_widget(Clear, area);
+    f.render_widget(block.clone(), area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(block.inner(area));
+
+    // Narrow popups show fewer weeks, scrolled so the picked day stays in view.
+    let weeks = (chunks[0].width.saturating_sub(label_width) as usize)
+        .clamp(1, stats::ACTIVITY_WEEKS) as u64;
+    let first = (stats::week_start(today) - Days::new((weeks - 1) * 7))
+        .min(stats::week_start(app.activity_day));
+
+    let mut months = " ".repeat(label_width as usize);
+    for week in 0..weeks {
+        let start = first + Days::new(week * 7);
+        let end = start + Days::new(6);
+        let column = label_width as usize + week as usize;
+        if (start.day() == 1 || end.month() != start.month()) && months.chars().count() <= column {
+            months.push_str(&" ".repeat(column - months.chars().count()));
+            months.push_str(&end.format("%b").to_string());
+        }
+    }
+    let mut lines = vec![Line::styled(months, Style::default().fg(theme.footer_text))];
+    for weekday in 0..7 {
+        let label = match weekday {
+            0 => "Mon ",
+            2 => "Wed ",
+            4 => "Fri ",
+            _ => "    ",
+        };
+        let mut spans = vec![Span::styled(label, Style::default().fg(theme.footer_text))];
+        for week in 0..weeks {
+            let date = first + Days::new(week * 7 + weekday);
+            if date > today {
+                spans.push(Span::raw(" "));
+                continue;
+            }
+            let episodes = days.get(&date).copied().unwrap_or(0);
+            let level = if episodes == 0 {
+                0
+            } else {
+                (episodes * 4).div_ceil(busiest).clamp(1, 4) as u8
+            };
+            let style = if date == app.activity_day {
+                Style::default()
+                    .fg(theme.highlight_fg)
+                    .bg(theme.highlight_bg)
+            } else {
+                Style::default().fg(theme.activity_color(level))
+            };
+            spans.push(Span::styled(ACTIVITY_GLYPHS[level as usize], style));
+        }
+        lines.push(Line::from(spans));
+    }
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let plural = |n: u32, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    let streaks = format!(
+        "Current streak: {}, longest: {}",
+        plural(stats::current_streak(&days, today), "day"),
+        plural(stats::longest_streak(&days), "day"),
+    );
+    f.render_widget(
+        Paragraph::new(streaks)
+            .style(Style::default().fg(theme.text))
+            .alignment(Alignment::Center),
+        chunks[2],
+    );
+
+    let watched = stats::watched_on(&app.entry, app.activity_day);
+    let episodes = days.get(&app.activity_day).copied().unwrap_or(0);
+    let heading = if episodes == 0 {
+        format!(
+            "{}: nothing watched",
+            app.activity_day.format("%a %Y-%m-%d")
+        )
+    } else {
+        format!(
+            "{}: {}",
+            app.activity_day.format("%a %Y-%m-%d"),
+            plural(episodes, "episode")
+        )
+    };
+    f.render_widget(
+        Paragraph::new(heading).style(Style::default().fg(theme.title)),
+        chunks[4],
+    );
+
+    let mut titles: Vec<Line> = watched
+        .iter()
+        .map(|show| Line::from(format!("{:>5}  {}", show.episodes, show.title)))
+        .collect();
+    if titles.len() > consts::ACTIVITY_DAY_ROWS {
+        let hidden = titles.len() - (consts::ACTIVITY_DAY_ROWS - 1);
+        titles.truncate(consts::ACTIVITY_DAY_ROWS - 1);
+        titles.push(Line::from(format!("       and {} more", hidden)));
+    }
+    f.render_widget(
+        Paragraph::new(titles).style(Style::default().fg(theme.text)),
+        chunks[5],
+    );
+
+    let help = Paragraph::new(format!(
+        "({})",
+        key_hints(
+            app,
+            &[
+                (&[KeyAction::Up, KeyAction::Down], "day"),
+                (&[KeyAction::Left, KeyAction::Right], "week"),
+                (&[KeyAction::Activity, KeyAction::ClearSelection], "close"),
+            ],
+            ", ",
+        )
+    ))
+    .style(Style::default().fg(theme.footer_text))
+    .alignment(Alignment::Center);
+    f.render_widget(help, chunks[6]);
+}
+
+fn draw_episode_progress_bar(f: &mut Frame, area: Rect, theme: &Theme, progress: u16) {
     if area.width == 0 {
         return;
     }
 
-    let total_cells = area.width as usize;
+    let progress_bar = Paragraph::new(progress_bar_line(theme, progress, area.width));
+    f.render_widget(progress_bar, area);
+}
+
+/// A bar of `width` cells, filled from the left by `progress` percent.
+fn progress_bar_line(theme: &Theme, progress: u16, width: u16) -> Line<'static> {
+    let total_cells = width as usize;
     let filled_cells = ((total_cells as u32 * progress as u32) + 50) / 100;
     let filled_cells = filled_cells.min(total_cells as u32) as usize;
 
     let spans = (0..total_cells)
         .map(|index| {
             let style = if index < filled_cells {
-                Style::default().fg(episode_progress_color(index, filled_cells))
+                Style::default().fg(theme.progress_color(index, filled_cells))
             } else {
-                Style::default().fg(consts::FOOTER_TEXT_COLOR)
+                Style::default().fg(theme.progress_empty)
             };
 
             Span::styled("█", style)
         })
         .collect::<Vec<_>>();
 
-    let progress_bar = Paragraph::new(Line::from(spans));
-    f.render_widget(progress_bar, area);
-}
-
-fn episode_progress_color(index: usize, filled_cells: usize) -> Color {
-    let (red, green, blue) = EPISODE_PROGRESS_BASE_COLOR;
-
-    let brightness = if filled_cells <= 1 {
-        1.0
-    } else {
-        let step = index as f32 / (filled_cells - 1) as f32;
-        1.0 - (1.0 - EPISODE_PROGRESS_MIN_BRIGHTNESS) * step
-    };
-
-    Color::Rgb(
-        scale_channel(red, brightness),
-        scale_channel(green, brightness),
-        scale_channel(blue, brightness),
-    )
-}
-
-fn scale_channel(channel: u8, brightness: f32) -> u8 {
-    ((channel as f32 * brightness).round()).clamp(0.0, 255.0) as u8
+    Line::from(spans)
 }
 
 fn draw_popup_input(f: &mut Frame, area: Rect, app: &mut App, title: &str) {
     let input = Paragraph::new(app.input.as_str())
-        .style(Style::default().fg(consts::TEXT_COLOR))
+        .style(Style::default().fg(app.theme.text))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(Style::default().fg(consts::BORDER_COLOR))
-                .title_style(Style::default().fg(consts::TITLE_COLOR)),
+                .border_style(Style::default().fg(app.theme.border))
+                .title_style(Style::default().fg(app.theme.title)),
         );
     f.render_widget(Clear, area);
     f.render_widget(input, area);
@@ -452,17 +1316,17 @@ fn draw_title_popup(f: &mut Frame, app: &App) {
             let block = Block::default()
                 .title("Full Title")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(consts::BORDER_COLOR))
-                .title_style(Style::default().fg(consts::TITLE_COLOR));
+                .border_style(Style::default().fg(app.theme.border))
+                .title_style(Style::default().fg(app.theme.title));
             let text = Paragraph::new(entry.title.as_str())
                 .block(block)
                 .wrap(Wrap { trim: true });
 
-            let area = centered_rect(
-                consts::TITLE_POPUP_WIDTH,
-                consts::TITLE_POPUP_HEIGHT,
-                f.size(),
-            );
+            let width = (text_width(&entry.title) + 2)
+                .clamp(12, consts::TITLE_POPUP_MAX_WIDTH)
+                .min(f.size().width);
+            let height = wrapped_rows(&entry.title, width.saturating_sub(2)) + 2;
+            let area = popup_rect(width, height, f.size());
             f.render_widget(Clear, area);
             f.render_widget(text, area);
         }
@@ -475,84 +1339,213 @@ fn draw_error_popup(f: &mut Frame, app: &mut App) {
             app.error = None;
             app.last_error_time = None;
         } else if let Some(error) = &app.error {
-            let max_width = (f.size().width as f32 * 0.8) as u16;
-            let width = (error.len() as u16 + 2).min(max_width);
-
-            let text_width = width.saturating_sub(2);
-            let wrapped_lines = if text_width > 0 {
-                error
-                    .lines()
-                    .map(|line| (line.chars().count() as u16 + text_width - 1) / text_width)
-                    .sum()
-            } else {
-                1
-            };
-            let height = wrapped_lines + 2;
+            draw_message_popup(
+                f,
+                app,
+                "Error",
+                error,
+                app.theme.error_border,
+                app.theme.error_text,
+            );
+        }
+    }
+}
 
-            let y = if let (InputMode::Adding | InputMode::Editing, Some(input_chunk)) =
-                (&app.input_mode, app.layout.get(1))
-            {
-                input_chunk.y.saturating_sub(height)
-            } else {
-                f.size().height.saturating_sub(height)
-            };
+fn draw_info_popup(f: &mut Frame, app: &mut App) {
+    if let Some(last_info_time) = app.last_info_time {
+        if last_info_time.elapsed().as_secs() > consts::ERROR_POPUP_DURATION {
+            app.info = None;
+            app.last_info_time = None;
+        } else if let Some(info) = &app.info {
+            draw_message_popup(f, app, "Info", info, app.theme.border, app.theme.text);
+        }
+    }
+}
 
-            let area = Rect {
-                x: f.size().width.saturating_sub(width) / 2,
-                y,
-                width,
-                height,
-            };
+fn draw_message_popup(
+    f: &mut Frame,
+    app: &App,
+    title: &str,
+    message: &str,
+    border_color: Color,
+    text_color: Color,
+) {
+    let max_width = (f.size().width as f32 * 0.8) as u16;
+    let width = (message.len() as u16 + 2).min(max_width);
+
+    let text_width = width.saturating_sub(2);
+    let wrapped_lines = if text_width > 0 {
+        message
+            .lines()
+            .map(|line| (line.chars().count() as u16).div_ceil(text_width))
+            .sum()
+    } else {
+        1
+    };
+    let height = wrapped_lines + 2;
 
-            let block = Block::default()
-                .title("Error")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(consts::ERROR_BORDER_COLOR))
-                .title_style(Style::default().fg(consts::TITLE_COLOR));
-            let text = Paragraph::new(error.as_str())
-                .block(block)
-                .wrap(Wrap { trim: true })
-                .style(Style::default().fg(consts::ERROR_TEXT_COLOR));
+    let y = if let (true, Some(input_chunk)) = (app.input_mode.uses_input_bar(), app.layout.get(1))
+    {
+        input_chunk.y.saturating_sub(height)
+    } else {
+        f.size().height.saturating_sub(height)
+    };
 
-            f.render_widget(Clear, area);
-            f.render_widget(text, area);
-        }
-    }
+    let area = Rect {
+        x: f.size().width.saturating_sub(width) / 2,
+        y,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .title_style(Style::default().fg(app.theme.title));
+    let text = Paragraph::new(message)
+        .block(block)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(text_color));
+
+    f.render_widget(Clear, area);
+    f.render_widget(text, area);
 }
 
-fn draw_confirmation_popup(f: &mut Frame, app: &App) {
-    let area = centered_rect(
-        consts::CONFIRMATION_POPUP_WIDTH,
-        consts::CONFIRMATION_POPUP_HEIGHT,
-        f.size(),
+fn draw_import_popup(f: &mut Frame, app: &mut App) {
+    let Some(preview) = &app.pending_import else {
+        return;
+    };
+
+    let (merge, replace) = (&preview.merge, &preview.replace);
+    let title = format!("Import Preview ({} entries)", preview.incoming);
+    let merge_text = format!("Merge:   {}", merge.report);
+    let replace_text = format!("Replace: {}", replace.report);
+    let help_text = "m: merge | r: replace | up/down: scroll | (esc: cancel)";
+
+    let report = &merge.report;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut push_section =
+        |title: String, items: Vec<String>| {
+            if items.is_empty() {
+                return;
+            }
+            lines.push(Line::styled(title, Style::default().fg(app.theme.title)));
+            lines.extend(items.into_iter().map(|item| {
+                Line::styled(format!("  {}", item), Style::default().fg(app.theme.text))
+            }));
+        };
+    push_section(
+        format!("New ({})", report.added.len()),
+        report.added.clone(),
+    );
+    push_section(
+        format!("Updated ({})", report.updated.len()),
+        report.updated.clone(),
+    );
+    push_section(
+        format!("Skipped ({})", report.skipped.len()),
+        report
+            .skipped
+            .iter()
+            .map(|(title, reason)| format!("{} ({})", title, reason))
+            .collect(),
+    );
+    push_section(
+        format!("Removed on replace ({})", replace.report.removed.len()),
+        replace.report.removed.clone(),
     );
 
+    // The summary, the "Merge changes" rule and the help take four rows, the border two.
+    let content_width = lines
+        .iter()
+        .map(|line| line.width() as u16)
+        .chain([&title, &merge_text, &replace_text, help_text].map(text_width))
+        .max()
+        .unwrap_or(0);
+    let area = popup_rect(content_width + 4, lines.len() as u16 + 6, f.size());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title))
+        .padding(Padding::new(1, 1, 0, 0));
+
+    f.render_widget(Clear, area);
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner_area);
+
+    let merge_p = Paragraph::new(merge_text).style(Style::default().fg(app.theme.text));
+    let replace_p = Paragraph::new(replace_text).style(Style::default().fg(app.theme.text));
+    f.render_widget(merge_p, chunks[0]);
+    f.render_widget(replace_p, chunks[1]);
+
+    let max_scroll = (lines.len() as u16).saturating_sub(chunks[2].height.saturating_sub(1));
+    app.import_preview_scroll = app.import_preview_scroll.min(max_scroll);
+    let changes = Paragraph::new(lines)
+        .scroll((app.import_preview_scroll, 0))
+        .block(
+            Block::default()
+                .title("Merge changes")
+                .borders(Borders::TOP)
+                .border_style(Style::default().fg(app.theme.border))
+                .title_style(Style::default().fg(app.theme.title)),
+        );
+    f.render_widget(changes, chunks[2]);
+
+    let help_p = Paragraph::new(help_text)
+        .style(Style::default().fg(app.theme.footer_text))
+        .alignment(Alignment::Center);
+    f.render_widget(help_p, chunks[3]);
+}
+
+fn draw_confirmation_popup(f: &mut Frame, app: &App) {
+    let entries = match app.selected_entries().len() {
+        0 | 1 => "this entry".to_string(),
+        picked => format!("these {} entries", picked),
+    };
     let (title, message) = match app.input_mode {
         InputMode::ConfirmDelete => {
             if app.show_dropped {
                 (
                     "Confirm Deletion",
-                    "Are you sure you want to delete this entry? (y/n)",
+                    format!("Are you sure you want to delete {}? (y/n)", entries),
                 )
             } else {
                 (
                     "Confirm Drop",
-                    "Are you sure you want to drop this entry? (y/n)",
+                    format!("Are you sure you want to drop {}? (y/n)", entries),
                 )
             }
         }
         InputMode::ConfirmDeleteAllDropped => (
             "Confirm Deletion",
-            "Are you sure you want to delete ALL dropped entries? (y/n)",
+            "Are you sure you want to delete ALL dropped entries? (y/n)".to_string(),
         ),
         _ => return, // Should not happen
     };
 
+    // Border, padding and margin around the message, plus an empty row below it.
+    let (chrome_x, chrome_y) = (8, 7);
+    let width = (text_width(&message) + chrome_x).min(f.size().width);
+    let rows = wrapped_rows(&message, width.saturating_sub(chrome_x));
+    let area = popup_rect(width, rows + chrome_y, f.size());
+
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(consts::BORDER_COLOR))
-        .title_style(Style::default().fg(consts::TITLE_COLOR))
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title))
         .padding(Padding::new(2, 2, 1, 1));
 
     f.render_widget(Clear, area);
@@ -565,21 +1558,59 @@ fn draw_confirmation_popup(f: &mut Frame, app: &App) {
         .split(block.inner(area));
 
     let paragraph = Paragraph::new(message)
-        .style(Style::default().fg(consts::TEXT_COLOR))
-        .alignment(Alignment::Center);
+        .style(Style::default().fg(app.theme.text))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, chunks[0]);
 }
 
 fn draw_dropped_popup(f: &mut Frame, app: &mut App) {
-    let area = f.size();
     let dropped_entries = app.get_dropped_entries();
+    let title = match app.selected_entries().len() {
+        0 => format!("Dropped Entries ({})", dropped_entries.len()),
+        picked => format!(
+            "Dropped Entries ({}, {} selected)",
+            dropped_entries.len(),
+            picked
+        ),
+    };
+    let help_text = dropped_help_text(app);
+
+    // Border and margin around the list, a scrollbar, plus the help row.
+    let (chrome_x, chrome_y) = (5, 5);
+    let screen = f.size();
+    let item_width = dropped_entries
+        .iter()
+        .map(|(_, s)| {
+            let marker = selection_marker(app, s).width();
+            let suffix = format!(" (S{} E{})", s.season, s.episode);
+            (marker + s.title.chars().count() + suffix.chars().count() + consts::PADDING) as u16
+        })
+        .max()
+        .unwrap_or(0);
+    let max_rows = screen.height.saturating_sub(chrome_y) as usize;
+    // Two columns when one would need scrolling and the screen is wide enough for both.
+    let dropped_is_two_column =
+        dropped_entries.len() > max_rows && screen.width.saturating_sub(chrome_x) > 80;
+    app.dropped_is_two_column = dropped_is_two_column;
+
+    let (list_width, rows) = if dropped_is_two_column {
+        (screen.width, dropped_entries.len().div_ceil(2))
+    } else {
+        (item_width, dropped_entries.len())
+    };
+    let width = list_width
+        .max(text_width(&help_text))
+        .max(text_width(&title) + 2)
+        + chrome_x;
+    let area = popup_rect(width, rows.max(1) as u16 + chrome_y, screen);
 
     let block = Block::default()
-        .title(format!("Dropped Entries ({})", dropped_entries.len()))
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(consts::BORDER_COLOR))
-        .title_style(Style::default().fg(consts::TITLE_COLOR));
+        .border_style(Style::default().fg(app.theme.border))
+        .title_style(Style::default().fg(app.theme.title));
 
     f.render_widget(Clear, area);
     let inner_area = block.inner(area);
@@ -591,70 +1622,37 @@ fn draw_dropped_popup(f: &mut Frame, app: &mut App) {
         .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
         .split(inner_area);
 
-    let dropped_is_two_column = chunks[0].width > 80;
-    app.dropped_is_two_column = dropped_is_two_column;
-
-    if dropped_is_two_column {
-        let columns = Layout::default()
+    let columns = if dropped_is_two_column {
+        Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(chunks[0]);
-        app.dropped_column_layout = columns.to_vec();
-
-        let mid_point = (dropped_entries.len() + 1) / 2;
-        let (left_entries, right_entries) = dropped_entries.split_at(mid_point);
-
-        for (i, entries) in [left_entries, right_entries].iter().enumerate() {
-            let items: Vec<ListItem> = entries
-                .iter()
-                .map(|(_, s)| {
-                    let col_width = columns[i].width as usize;
-                    let suffix = format!(" (S{} E{})", s.season, s.episode);
-                    let suffix_len = suffix.chars().count();
-                    let max_title_chars = if col_width > suffix_len + consts::PADDING {
-                        col_width - suffix_len - consts::PADDING
-                    } else {
-                        0
-                    };
-
-                    let title = if s.title.chars().count() > max_title_chars {
-                        let take = max_title_chars.saturating_sub(3);
-                        let mut truncated_title = s.title.chars().take(take).collect::<String>();
-                        truncated_title.push_str("...");
-                        truncated_title
-                    } else {
-                        s.title.clone()
-                    };
-                    ListItem::new(format!("{} (S{} E{})", title, s.season, s.episode))
-                        .style(Style::default().fg(consts::TEXT_COLOR))
-                })
-                .collect();
-
-            let list = List::new(items).highlight_style(
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .bg(consts::HIGHLIGHT_BG)
-                    .fg(consts::HIGHLIGHT_FG),
-            );
-
-            let mut state = ListState::default();
-            if let Some(selected_in_col) = entries
-                .iter()
-                .position(|(idx, _)| *idx == app.selected_index)
-            {
-                state.select(Some(selected_in_col));
+            .split(chunks[0])
+            .to_vec()
+    } else {
+        vec![chunks[0]]
+    };
+    app.dropped_column_layout = columns.clone();
+
+    for (col, area) in columns.into_iter().enumerate() {
+        let entries = app.dropped_column(col);
+        let rows = area.height as usize;
+        // Leave the last cell of each row to the scrollbar.
+        let list_area = if entries.len() > rows {
+            Rect {
+                width: area.width.saturating_sub(1),
+                ..area
             }
+        } else {
+            area
+        };
 
-            f.render_stateful_widget(list, columns[i], &mut state);
-        }
-    } else {
-        app.dropped_column_layout = vec![chunks[0]];
-        let items: Vec<ListItem> = dropped_entries
+        let items: Vec<ListItem> = entries
             .iter()
             .map(|(_, s)| {
-                let col_width = chunks[0].width as usize;
+                let col_width = list_area.width as usize;
                 let suffix = format!(" (S{} E{})", s.season, s.episode);
-                let suffix_len = suffix.chars().count();
+                let marker = selection_marker(app, s);
+                let suffix_len = suffix.chars().count() + marker.width();
                 let max_title_chars = if col_width > suffix_len + consts::PADDING {
                     col_width - suffix_len - consts::PADDING
                 } else {
@@ -669,98 +1667,107 @@ fn draw_dropped_popup(f: &mut Frame, app: &mut App) {
                 } else {
                     s.title.clone()
                 };
-                ListItem::new(format!("{} (S{} E{})", title, s.season, s.episode))
-                    .style(Style::default().fg(consts::TEXT_COLOR))
+                ListItem::new(Line::from(vec![marker, Span::raw(title + &suffix)]))
+                    .style(Style::default().fg(app.theme.text))
             })
             .collect();
 
         let list = List::new(items).highlight_style(
             Style::default()
                 .add_modifier(Modifier::BOLD)
-                .bg(consts::HIGHLIGHT_BG)
-                .fg(consts::HIGHLIGHT_FG),
+                .bg(app.theme.highlight_bg)
+                .fg(app.theme.highlight_fg),
         );
 
-        let mut state = ListState::default();
-        if let Some(selected_in_dropped) = dropped_entries
+        let selected = entries
             .iter()
-            .position(|(idx, _)| *idx == app.selected_index)
-        {
-            state.select(Some(selected_in_dropped));
-        }
-
-        f.render_stateful_widget(list, chunks[0], &mut state);
+            .position(|(idx, _)| *idx == app.selected_index);
+        let offset = scroll::offset_for(app.dropped_offsets[col], selected, entries.len(), rows);
+        app.dropped_offsets[col] = offset;
+
+        let mut state = ListState::default()
+            .with_offset(offset)
+            .with_selected(selected);
+        f.render_stateful_widget(list, list_area, &mut state);
+        draw_scrollbar(f, area, &app.theme, entries.len(), offset, rows);
     }
 
-    let help_text = "r: reactivate | x: delete | (esc: close)";
     let paragraph = Paragraph::new(help_text)
-        .style(Style::default().fg(consts::FOOTER_TEXT_COLOR))
+        .style(Style::default().fg(app.theme.footer_text))
         .alignment(Alignment::Center);
 
     f.render_widget(paragraph, chunks[1]);
 }
 
-pub fn get_mouse_selection(app: &mut App) -> Option<usize> {
-    let mouse_x = app.mouse_pos.0;
-    let mouse_y = app.mouse_pos.1;
+fn dropped_help_text(app: &App) -> String {
+    format!(
+        "{} | ({})",
+        key_hints(
+            app,
+            &[
+                (&[KeyAction::Reactivate], "reactivate"),
+                (&[KeyAction::Remove], "delete"),
+            ],
+            " | ",
+        ),
+        key_hints(app, &[(&[KeyAction::Close], "close")], ""),
+    )
+}
+
+/// The board entry under the mouse, in whichever columns are shown.
+pub fn get_mouse_selection(app: &App) -> Option<usize> {
+    let (mouse_x, mouse_y) = app.mouse_pos;
+    let col = app.column_layout.iter().position(|r| {
+        mouse_x >= r.x && mouse_x < r.x + r.width && mouse_y >= r.y && mouse_y < r.y + r.height
+    })?;
+    let status = Status::BOARD[col];
+    let list_start_y = app.column_layout[col].y + 1;
+    let offset = app.column_offsets.get(&status).copied().unwrap_or(0);
+    let row = mouse_y.checked_sub(list_start_y)? as usize + offset;
+    app.get_entries_by_status(status)
+        .get(row)
+        .map(|(idx, _)| *idx)
+}
 
-    for (i, status) in [Status::Planning, Status::Watching, Status::Completed]
+/// The tab under the mouse while the board shows one column at a time.
+pub fn get_mouse_tab(app: &App) -> Option<Status> {
+    let (mouse_x, mouse_y) = app.mouse_pos;
+    app.tab_layout
         .iter()
-        .enumerate()
-    {
-        let col = app.column_layout[i];
-        if mouse_x >= col.x && mouse_x < col.x + col.width {
-            let entry_in_status: Vec<(usize, &crate::Entry)> = app
-                .entry
-                .iter()
-                .enumerate()
-                .filter(|(_, s)| &s.status == status)
-                .collect();
-
-            let list_start_y = col.y + 1;
-            if mouse_y >= list_start_y {
-                let selected_line = (mouse_y - list_start_y) as usize;
-                if let Some((original_index, _)) = entry_in_status.get(selected_line) {
-                    return Some(*original_index);
-                }
-            }
-        }
-    }
-    None
+        .position(|r| {
+            mouse_x >= r.x && mouse_x < r.x + r.width && mouse_y >= r.y && mouse_y < r.y + r.height
+        })
+        .map(|tab| Status::BOARD[tab])
 }
 
-pub fn get_dropped_mouse_selection(app: &mut App) -> Option<usize> {
+/// Where a dragged entry is dropped: a tab, or the column at the mouse's x position, at any
+/// height.
+pub fn get_mouse_column(app: &App) -> Option<Status> {
     let mouse_x = app.mouse_pos.0;
-    let mouse_y = app.mouse_pos.1;
+    get_mouse_tab(app).or_else(|| {
+        app.column_layout
+            .iter()
+            .position(|r| mouse_x >= r.x && mouse_x < r.x + r.width)
+            .map(|col| Status::BOARD[col])
+    })
+}
 
-    let dropped_entries = app.get_dropped_entries();
+/// The column of the Dropped list under the mouse, at any height.
+pub fn get_dropped_mouse_column(app: &App) -> Option<usize> {
+    let mouse_x = app.mouse_pos.0;
+    app.dropped_column_layout
+        .iter()
+        .position(|r| mouse_x >= r.x && mouse_x < r.x + r.width)
+}
 
-    if app.dropped_column_layout.is_empty() {
+pub fn get_dropped_mouse_selection(app: &App) -> Option<usize> {
+    let mouse_y = app.mouse_pos.1;
+    let col = get_dropped_mouse_column(app)?;
+    let area = app.dropped_column_layout[col];
+    if mouse_y >= area.y + area.height {
         return None;
     }
-
-    for (col_idx, col_rect) in app.dropped_column_layout.iter().enumerate() {
-        if mouse_x >= col_rect.x && mouse_x < col_rect.x + col_rect.width {
-            let list_start_y = col_rect.y + 1;
-            if mouse_y >= list_start_y {
-                let selected_line = (mouse_y - list_start_y) as usize;
-
-                let entries_in_col: Vec<(usize, Entry)> = if app.dropped_is_two_column {
-                    let mid_point = (dropped_entries.len() + 1) / 2;
-                    if col_idx == 0 {
-                        dropped_entries[..mid_point].to_vec()
-                    } else {
-                        dropped_entries[mid_point..].to_vec()
-                    }
-                } else {
-                    dropped_entries.clone()
-                };
-
-                if let Some((original_index, _)) = entries_in_col.get(selected_line) {
-                    return Some(*original_index);
-                }
-            }
-        }
-    }
-    None
+    // The dropped lists have no border of their own.
+    let row = mouse_y.checked_sub(area.y)? as usize + app.dropped_offsets[col];
+    app.dropped_column(col).get(row).map(|(idx, _)| *idx)
 }
@@ -0,0 +1,330 @@
+mod common;
+
+use chrono::Datelike;
+use common::{press, temp_app, type_text};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use senarai::action::Action;
+use senarai::app::{AddError, App, InputMode};
+use senarai::formats::json;
+use senarai::import::ImportMode;
+use senarai::input::{self, InputResult};
+use senarai::sort::SortMode;
+use senarai::{database, Status};
+use std::path::Path;
+
+#[test]
+fn test_add_and_rename_through_actions() {
+    let mut app = temp_app("actions-rename");
+
+    app.dispatch(Action::StartInput(InputMode::Adding));
+    type_text(&mut app, "the office");
+    app.dispatch(Action::SubmitInput);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.entry[0].title, "The Office");
+
+    app.dispatch(Action::StartInput(InputMode::Editing));
+    assert_eq!(app.input, "The Office");
+    for _ in 0.."Office".len() {
+        app.dispatch(Action::DeleteBackward);
+    }
+    type_text(&mut app, "wire");
+    app.dispatch(Action::SubmitInput);
+
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored[0].title, "The Wire");
+    assert!(app.error.is_none());
+
+    assert_eq!(
+        app.add_entry("The Wire".to_string()),
+        Err(AddError::Duplicate("The Wire".to_string()))
+    );
+    app.dispatch(Action::AddEntry("The Wire".to_string()));
+    assert!(app.error.as_ref().unwrap().contains("already exists"));
+    assert_eq!(app.entry.len(), 1);
+}
+
+#[test]
+fn test_keys_with_count_prefix_drive_the_app() {
+    let mut app = temp_app("actions-keys");
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+
+    press(&mut app, KeyCode::Char('5'));
+    assert_eq!(app.pending_keys_text().as_deref(), Some("5"));
+    assert_eq!(press(&mut app, KeyCode::Char('+')), InputResult::Modified);
+    assert_eq!(app.entry[0].episode, 5);
+    assert_eq!(app.pending_keys_text(), None);
+
+    press(&mut app, KeyCode::Char('o'));
+    assert_eq!(app.input_mode, InputMode::TotalEpisodes);
+    press(&mut app, KeyCode::Char('#'));
+    press(&mut app, KeyCode::Char('1'));
+    press(&mut app, KeyCode::Char('x'));
+    press(&mut app, KeyCode::Char('0'));
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.entry[0].max_episodes, 10);
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.input_mode, InputMode::Normal);
+
+    app.dispatch(Action::MoveTo(Status::Completed));
+    assert_eq!(press(&mut app, KeyCode::Char('q')), InputResult::Quit);
+
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored[0].episode, 5);
+    assert_eq!(stored[0].watch_log.values().sum::<u32>(), 5);
+    assert_eq!(stored[0].max_episodes, 10);
+    assert_eq!(stored[0].status, Status::Completed);
+}
+
+#[test]
+fn test_remove_asks_for_confirmation() {
+    let mut app = temp_app("actions-remove");
+    app.dispatch(Action::AddEntry("Lost".to_string()));
+
+    app.dispatch(Action::RequestRemove);
+    assert_eq!(app.input_mode, InputMode::ConfirmDelete);
+    app.dispatch(Action::Cancel);
+    assert_eq!(app.entry[0].status, Status::Planning);
+
+    app.dispatch(Action::RequestRemove);
+    app.dispatch(Action::ConfirmRemove);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.entry[0].status, Status::Dropped);
+
+    app.dispatch(Action::OpenDropped);
+    app.dispatch(Action::RequestRemove);
+    app.dispatch(Action::ConfirmRemove);
+    assert_eq!(app.input_mode, InputMode::Dropped);
+    assert!(app.entry.is_empty());
+}
+
+#[test]
+fn test_updates_are_timestamped_and_sort_modes_persist() {
+    let mut app = temp_app("actions-sort");
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+    app.dispatch(Action::AddEntry("Andor".to_string()));
+    let added = database::load_entry(&app.config).unwrap();
+    assert!(added.iter().all(|e| e.updated_at.is_some()));
+
+    // Only the entry that changed gets a new timestamp.
+    let older = chrono::NaiveDate::from_ymd_opt(2020, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0);
+    for entry in app.entry.iter_mut() {
+        entry.updated_at = older;
+    }
+    database::update_all_entries(&app.entry, &app.config).unwrap();
+    app.selected_index = 0;
+    app.dispatch(Action::NextEpisode(1));
+    let stored = database::load_entry(&app.config).unwrap();
+    assert!(stored[0].updated_at > older);
+    assert_eq!(stored[1].updated_at, older);
+
+    // Reordering alone isn't an update.
+    let watched = stored[0].updated_at;
+    app.dispatch(Action::MoveDown(1));
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored[0].updated_at, older);
+    assert_eq!(stored[1].updated_at, watched);
+
+    app.dispatch(Action::RunCommand("sort updated".to_string()));
+    assert_eq!(
+        database::load_sort_modes(&app.config).unwrap(),
+        vec![(Status::Planning, SortMode::Updated)]
+    );
+    app.dispatch(Action::SetSort(None));
+    assert_eq!(app.sort_mode(Status::Planning), SortMode::Remaining);
+}
+
+fn titles(app: &App, status: Status) -> Vec<String> {
+    app.get_entries_by_status(status)
+        .iter()
+        .map(|(_, e)| e.title.clone())
+        .collect()
+}
+
+#[test]
+fn test_bulk_move_tag_and_reorder() {
+    let mut app = temp_app("actions-bulk");
+    for title in ["A", "B", "C", "D", "E"] {
+        app.dispatch(Action::AddEntry(title.to_string()));
+    }
+
+    // Space picks B and moves on to C, V extends the pick down to D.
+    app.dispatch(Action::Select(1));
+    press(&mut app, KeyCode::Char(' '));
+    assert_eq!(app.entry[app.selected_index].title, "C");
+    app.dispatch(Action::SelectNext);
+    press(&mut app, KeyCode::Char('V'));
+    assert_eq!(app.selected_entries(), vec![1, 2, 3]);
+
+    // The second row up is blocked by the top of the column.
+    app.dispatch(Action::MoveUp(2));
+    assert_eq!(titles(&app, Status::Planning), ["B", "C", "D", "A", "E"]);
+    app.dispatch(Action::MoveDown(1));
+    assert_eq!(titles(&app, Status::Planning), ["A", "B", "C", "D", "E"]);
+
+    app.dispatch(Action::RunCommand("tag Anime".to_string()));
+    let stored = database::load_entry(&app.config).unwrap();
+    let tagged: Vec<&str> = stored
+        .iter()
+        .filter(|e| e.has_tag("anime"))
+        .map(|e| e.title.as_str())
+        .collect();
+    assert_eq!(tagged, ["B", "C", "D"]);
+    app.dispatch(Action::SetFilter("tag:ani".to_string()));
+    assert_eq!(titles(&app, Status::Planning), ["B", "C", "D"]);
+    app.dispatch(Action::SetFilter(String::new()));
+
+    app.dispatch(Action::MoveTo(Status::Watching));
+    assert!(app.selection.is_empty());
+    assert_eq!(titles(&app, Status::Watching), ["B", "C", "D"]);
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(
+        stored
+            .iter()
+            .filter(|e| e.status == Status::Watching)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn test_bulk_drop_and_delete_confirm_once() {
+    let mut app = temp_app("actions-bulk-remove");
+    for title in ["A", "B", "C"] {
+        app.dispatch(Action::AddEntry(title.to_string()));
+    }
+    app.dispatch(Action::Select(0));
+    press(&mut app, KeyCode::Char(' '));
+    press(&mut app, KeyCode::Char(' '));
+
+    press(&mut app, KeyCode::Char('x'));
+    assert_eq!(app.input_mode, InputMode::ConfirmDelete);
+    press(&mut app, KeyCode::Char('y'));
+    assert_eq!(titles(&app, Status::Planning), ["C"]);
+    assert_eq!(app.get_dropped_entries().len(), 2);
+
+    press(&mut app, KeyCode::Char('d'));
+    press(&mut app, KeyCode::Char(' '));
+    press(&mut app, KeyCode::Char(' '));
+    press(&mut app, KeyCode::Char('x'));
+    press(&mut app, KeyCode::Char('y'));
+    assert!(app.get_dropped_entries().is_empty());
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].title, "C");
+}
+
+#[test]
+fn test_activity_calendar_picks_days_from_the_saved_log() {
+    let mut app = temp_app("actions-activity");
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+    press(&mut app, KeyCode::Char('+'));
+    press(&mut app, KeyCode::Char('+'));
+
+    let today = database::now().date();
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored[0].watch_log.get(&today), Some(&2));
+
+    press(&mut app, KeyCode::Char('A'));
+    assert_eq!(app.input_mode, InputMode::Activity);
+    assert_eq!(app.activity_day, today);
+    // Later days haven't happened yet.
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.activity_day, today);
+    press(&mut app, KeyCode::Left);
+    press(&mut app, KeyCode::Up);
+    assert_eq!((today - app.activity_day).num_days(), 8);
+    press(&mut app, KeyCode::Char('3'));
+    press(&mut app, KeyCode::Right);
+    assert_eq!(app.activity_day, today);
+    press(&mut app, KeyCode::Home);
+    assert_eq!(app.activity_day.weekday(), chrono::Weekday::Mon);
+    assert!((today - app.activity_day).num_days() >= 364);
+
+    // Keys of the board don't reach it while the calendar is open.
+    assert_eq!(press(&mut app, KeyCode::Char('+')), InputResult::Success);
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert_eq!(app.entry[0].episode, 2);
+}
+
+#[test]
+fn test_line_editing_keys_and_paste_in_the_title_input() {
+    let mut app = temp_app("actions-line-editing");
+    let key = |app: &mut App, code: KeyCode, modifiers: KeyModifiers| {
+        input::handle_key(KeyEvent::new(code, modifiers), app);
+    };
+    let ctrl = |app: &mut App, c: char| key(app, KeyCode::Char(c), KeyModifiers::CONTROL);
+    let alt = |app: &mut App, c: char| key(app, KeyCode::Char(c), KeyModifiers::ALT);
+
+    app.dispatch(Action::StartInput(InputMode::Adding));
+    // Pasted text keeps its case and loses the trailing line break.
+    input::handle_paste("the Café de l'Europe\r\n".to_string(), &mut app);
+    assert_eq!(app.input, "the Café de l'Europe");
+    assert_eq!(app.cursor_position, 20);
+
+    ctrl(&mut app, 'w');
+    assert_eq!(app.input, "the Café de ");
+    alt(&mut app, 'b');
+    alt(&mut app, 'b');
+    assert_eq!(app.cursor_position, 4);
+    alt(&mut app, 'f');
+    assert_eq!(app.cursor_position, 8);
+    alt(&mut app, 'd');
+    assert_eq!(app.input, "the Café ");
+
+    ctrl(&mut app, 'a');
+    assert_eq!(app.cursor_position, 0);
+    alt(&mut app, 'd');
+    assert_eq!(
+        (app.input.as_str(), app.kill_buffer.as_str()),
+        (" Café ", "the")
+    );
+    ctrl(&mut app, 'e');
+    ctrl(&mut app, 'y');
+    assert_eq!(app.input, " Café the");
+
+    key(&mut app, KeyCode::Left, KeyModifiers::CONTROL);
+    ctrl(&mut app, 'k');
+    ctrl(&mut app, 'u');
+    assert_eq!(
+        (app.input.as_str(), app.kill_buffer.as_str()),
+        ("", " Café ")
+    );
+    ctrl(&mut app, 'y');
+    key(&mut app, KeyCode::Home, KeyModifiers::NONE);
+    // Other control keys don't type anything.
+    ctrl(&mut app, 'x');
+    assert_eq!(app.input, " Café ");
+
+    // Pasting into a number prompt keeps only the digits.
+    app.dispatch(Action::Cancel);
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+    app.dispatch(Action::OpenEpisodes);
+    app.dispatch(Action::StartInput(InputMode::MaxEpisodes));
+    input::handle_paste("2 6\n".to_string(), &mut app);
+    assert_eq!(app.input, "26");
+}
+
+#[test]
+fn test_import_preview_plans_once_and_applies_the_chosen_plan() {
+    let mut app = temp_app("actions-import");
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+    app.dispatch(Action::AddEntry("Lost".to_string()));
+    let path = format!("{}/list.json", app.config.storage_path);
+    json::export(&app.entry[..1], Path::new(&path)).unwrap();
+
+    app.dispatch(Action::LoadImport(path));
+    assert_eq!(app.input_mode, InputMode::ConfirmImport);
+    let preview = app.pending_import.as_ref().unwrap();
+    assert_eq!(preview.incoming, 1);
+    assert_eq!(preview.merge.report.unchanged, 1);
+    assert_eq!(preview.replace.report.removed, ["Lost"]);
+
+    app.dispatch(Action::ApplyImport(ImportMode::Replace));
+    assert!(app.pending_import.is_none());
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].title, "Dark");
+}
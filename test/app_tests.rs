@@ -1,5 +1,10 @@
-use senarai::app::App;
+use chrono::NaiveDate;
+use senarai::action::Action;
+use senarai::app::{App, InputMode, LookupError};
 use senarai::config::Config;
+use senarai::formats::ics;
+use senarai::fuzzy::fuzzy_match;
+use senarai::sort::{title_sort_key, SortMode};
 use senarai::{Entry, Status};
 use uuid::Uuid;
 
@@ -13,6 +18,7 @@ fn create_dummy_app() -> App {
             status: Status::Watching,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
         Entry {
             id: Uuid::parse_str("772d2d49-9ce7-4db7-bd33-8dfb93617af4").unwrap(),
@@ -22,6 +28,7 @@ fn create_dummy_app() -> App {
             status: Status::Completed,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
         Entry {
             id: Uuid::parse_str("2cd6538f-944b-429e-b840-98ec89ed49ef").unwrap(),
@@ -31,10 +38,12 @@ fn create_dummy_app() -> App {
             status: Status::Planning,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
     ];
     let config = Config {
         storage_path: "dummy_path".to_string(),
+        ..Default::default()
     };
     App::new(entries, config)
 }
@@ -43,28 +52,100 @@ fn create_dummy_app() -> App {
 fn test_next_episode() {
     let mut app = create_dummy_app();
     app.selected_index = 0;
-    app.next_episode();
+    app.next_episode(1);
     assert_eq!(app.entry[0].episode, 2);
 
     app.selected_index = 2;
-    app.next_episode();
+    app.next_episode(1);
     assert_eq!(app.entry[2].episode, 1);
 }
 
+#[test]
+fn test_episode_counters_log_the_day() {
+    let mut app = create_dummy_app();
+    app.selected_index = 0;
+    app.next_episode(1);
+    app.increment_watched_episodes(1);
+    assert_eq!(app.entry[0].watch_log.values().sum::<u32>(), 2);
+
+    app.prev_episode(1);
+    app.decrement_watched_episodes(1);
+    assert!(app.entry[0].watch_log.is_empty());
+
+    // Nothing was logged, so there is nothing to take back.
+    app.selected_index = 1;
+    app.entry[1].watched_episodes = 3;
+    app.decrement_watched_episodes(1);
+    assert!(app.entry[1].watch_log.is_empty());
+}
+
+#[test]
+fn test_watching_an_episode_keeps_the_calendar_numbering() {
+    let mut app = create_dummy_app();
+    app.selected_index = 0;
+    app.entry[0].max_episodes = 4;
+    app.entry[0].next_air_date = NaiveDate::from_ymd_opt(2026, 3, 2);
+    let now = NaiveDate::from_ymd_opt(2026, 3, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+
+    // E2 airs on March 2nd, so once it is watched E3 follows a week later.
+    app.next_episode(1);
+    assert_eq!(
+        app.entry[0].next_air_date,
+        NaiveDate::from_ymd_opt(2026, 3, 9)
+    );
+    let out = ics::to_string(&app.entry[..1], now);
+    assert!(!out.contains("S1E2"));
+    assert!(!out.contains("DTSTART;VALUE=DATE:20260302"));
+    assert!(out.contains("SUMMARY:Test Entry 1 S1E3"));
+    assert!(out.contains("DTSTART;VALUE=DATE:20260309"));
+
+    app.prev_episode(1);
+    assert_eq!(
+        app.entry[0].next_air_date,
+        NaiveDate::from_ymd_opt(2026, 3, 2)
+    );
+}
+
+#[test]
+fn test_counted_steps_apply_at_once() {
+    let mut app = create_dummy_app();
+    app.selected_index = 0;
+    app.entry[0].next_air_date = NaiveDate::from_ymd_opt(2026, 3, 2);
+    app.next_episode(3);
+    assert_eq!(app.entry[0].episode, 4);
+    assert_eq!(app.entry[0].watch_log.values().sum::<u32>(), 3);
+    assert_eq!(
+        app.entry[0].next_air_date,
+        NaiveDate::from_ymd_opt(2026, 3, 23)
+    );
+
+    app.decrement_watched_episodes(5);
+    assert_eq!(app.entry[0].watched_episodes, 0);
+    assert!(app.entry[0].watch_log.is_empty());
+
+    // Going back stops at the first season.
+    app.entry[0].season = 2;
+    app.prev_episode(10);
+    assert_eq!((app.entry[0].season, app.entry[0].episode), (1, 0));
+}
+
 #[test]
 fn test_prev_episode() {
     let mut app = create_dummy_app();
     app.selected_index = 0;
-    app.prev_episode();
+    app.prev_episode(1);
     assert_eq!(app.entry[0].episode, 0);
 
-    app.prev_episode();
+    app.prev_episode(1);
     assert_eq!(app.entry[0].episode, 0);
     assert_eq!(app.entry[0].season, 1);
 
     app.entry[0].season = 2;
     app.entry[0].episode = 0;
-    app.prev_episode();
+    app.prev_episode(1);
     assert_eq!(app.entry[0].season, 1);
     assert_eq!(app.entry[0].episode, 0);
 }
@@ -73,7 +154,7 @@ fn test_prev_episode() {
 fn test_next_season() {
     let mut app = create_dummy_app();
     app.selected_index = 0;
-    app.next_season();
+    app.next_season(1);
     assert_eq!(app.entry[0].season, 2);
     assert_eq!(app.entry[0].episode, 0);
 }
@@ -89,6 +170,7 @@ fn test_move_to() {
             status: Status::Planning,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
         Entry {
             id: Uuid::new_v4(),
@@ -98,6 +180,7 @@ fn test_move_to() {
             status: Status::Planning,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
         Entry {
             id: Uuid::new_v4(),
@@ -107,6 +190,7 @@ fn test_move_to() {
             status: Status::Watching,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
         Entry {
             id: Uuid::new_v4(),
@@ -116,10 +200,12 @@ fn test_move_to() {
             status: Status::Completed,
             watched_episodes: 0,
             max_episodes: 0,
+            ..Default::default()
         },
     ];
     let config = Config {
         storage_path: "dummy_path".to_string(),
+        ..Default::default()
     };
     let mut app = App::new(entries, config);
 
@@ -187,3 +273,198 @@ fn test_selected_entry_progress_rules() {
     app.entry[0].watched_episodes = 12;
     assert_eq!(app.selected_entry_progress(), 100);
 }
+
+#[test]
+fn test_runtime_accounting() {
+    let mut app = create_dummy_app();
+
+    app.selected_index = 0;
+    app.entry[0].watched_episodes = 4;
+    app.entry[0].max_episodes = 10;
+    assert_eq!(app.entry[0].minutes_watched(), 0);
+
+    app.set_runtime(24);
+    assert_eq!(app.entry[0].minutes_watched(), 96);
+    assert_eq!(app.entry[0].minutes_remaining(), Some(144));
+
+    app.set_season_runtime(45);
+    assert_eq!(app.entry[0].runtime_for_season(1), 45);
+    assert_eq!(app.entry[0].runtime_for_season(2), 24);
+    assert_eq!(app.entry[0].minutes_remaining(), Some(270));
+
+    app.set_season_runtime(0);
+    assert_eq!(app.entry[0].runtime_for_season(1), 24);
+
+    app.entry[0].max_episodes = 0;
+    assert_eq!(app.entry[0].minutes_remaining(), None);
+
+    app.entry[2].runtime_minutes = 30;
+    app.entry[2].max_episodes = 12;
+    assert_eq!(app.total_minutes_remaining(Status::Planning), 360);
+    assert_eq!(app.total_minutes_watched(), 96);
+}
+
+#[test]
+fn test_format_minutes() {
+    assert_eq!(senarai::format_minutes(45), "45m");
+    assert_eq!(senarai::format_minutes(125), "2h 05m");
+}
+
+#[test]
+fn test_find_entry_by_prefix_or_id() {
+    let app = create_dummy_app();
+
+    assert_eq!(app.find_entry("test entry 2"), Ok(1));
+    assert_eq!(
+        app.find_entry("2cd6538f-944b-429e-b840-98ec89ed49ef"),
+        Ok(2)
+    );
+    assert!(matches!(
+        app.find_entry("Test"),
+        Err(LookupError::Ambiguous(_, titles)) if titles.len() == 3
+    ));
+    assert_eq!(
+        app.find_entry("Missing"),
+        Err(LookupError::NotFound("Missing".to_string()))
+    );
+}
+
+#[test]
+fn test_set_position_keeps_watched_count() {
+    let mut app = create_dummy_app();
+    app.selected_index = 0;
+    app.entry[0].watched_episodes = 3;
+
+    app.set_position(Some(2), Some(5));
+    assert_eq!((app.entry[0].season, app.entry[0].episode), (2, 5));
+    assert_eq!(app.entry[0].watched_episodes, 3);
+
+    app.set_position(None, Some(1));
+    assert_eq!((app.entry[0].season, app.entry[0].episode), (2, 1));
+}
+
+#[test]
+fn test_move_selection_within_column() {
+    let mut app = create_dummy_app();
+    for i in 4..8 {
+        app.entry.push(Entry {
+            title: format!("Test Entry {}", i),
+            status: Status::Watching,
+            ..Default::default()
+        });
+    }
+    // Watching now holds entries 0, 3, 4, 5 and 6.
+    app.selected_index = 0;
+
+    app.move_selection_in_column(3);
+    assert_eq!(app.selected_index, 5);
+    app.move_selection_in_column(10);
+    assert_eq!(app.selected_index, 6);
+    app.move_selection_in_column(-2);
+    assert_eq!(app.selected_index, 4);
+
+    app.select_row_in_column(Some(1));
+    assert_eq!(app.selected_index, 3);
+    app.select_row_in_column(None);
+    assert_eq!(app.selected_index, 6);
+    app.select_row_in_column(Some(0));
+    assert_eq!(app.selected_index, 0);
+    assert_eq!(app.entry[app.selected_index].status, Status::Watching);
+}
+
+#[test]
+fn test_fuzzy_match() {
+    assert_eq!(fuzzy_match("off", "The Office"), Some(vec![4, 5, 6]));
+    assert_eq!(fuzzy_match("brba", "Breaking Bad"), Some(vec![0, 1, 9, 10]));
+    assert_eq!(
+        fuzzy_match("bad BREAK", "Breaking Bad"),
+        Some(vec![0, 1, 2, 3, 4, 9, 10, 11])
+    );
+    assert_eq!(fuzzy_match("", "Dark"), Some(vec![]));
+    assert_eq!(fuzzy_match("dx", "Dark"), None);
+}
+
+#[test]
+fn test_filter_narrows_navigation_and_restores_selection() {
+    let mut app = create_dummy_app();
+    app.entry.push(Entry {
+        title: "Dark".to_string(),
+        status: Status::Watching,
+        ..Default::default()
+    });
+    app.selected_index = 0;
+
+    app.dispatch(Action::StartInput(InputMode::Filter));
+    for c in "ent3".chars() {
+        app.dispatch(Action::InsertChar(c));
+    }
+    // Only "Test Entry 3" is left, so it gets selected and navigation stays on it.
+    assert_eq!(app.selected_index, 2);
+    assert_eq!(app.get_entries_by_status(Status::Watching).len(), 0);
+    app.dispatch(Action::SelectNext);
+    app.dispatch(Action::SelectNextColumn);
+    assert_eq!(app.selected_index, 2);
+
+    app.dispatch(Action::DeleteBackward);
+    // "ent" matches every entry except "Dark".
+    assert_eq!(app.get_entries_by_status(Status::Watching).len(), 1);
+    app.dispatch(Action::SelectNextColumn);
+    assert_eq!(app.selected_index, 0);
+
+    app.dispatch(Action::Cancel);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    assert!(app.filter.is_empty());
+    assert_eq!(app.get_entries_by_status(Status::Watching).len(), 2);
+
+    app.selected_index = 3;
+    app.dispatch(Action::StartInput(InputMode::Filter));
+    app.dispatch(Action::InsertChar('2'));
+    assert_eq!(app.selected_index, 1);
+    app.dispatch(Action::SubmitInput);
+    assert_eq!(app.filter, "2");
+    app.dispatch(Action::StartInput(InputMode::Filter));
+    app.dispatch(Action::Cancel);
+    assert_eq!(app.selected_index, 3);
+}
+
+#[test]
+fn test_sort_modes_leave_manual_order_alone() {
+    let mut app = create_dummy_app();
+    for (title, watched, max) in [("The Wire", 5, 10), ("Andor", 9, 12), ("Dark", 0, 0)] {
+        app.entry.push(Entry {
+            title: title.to_string(),
+            status: Status::Watching,
+            watched_episodes: watched,
+            max_episodes: max,
+            ..Default::default()
+        });
+    }
+    let titles = |app: &App| -> Vec<String> {
+        app.get_entries_by_status(Status::Watching)
+            .iter()
+            .map(|(_, e)| e.title.clone())
+            .collect()
+    };
+
+    app.sort_modes.insert(Status::Watching, SortMode::Title);
+    assert_eq!(titles(&app), ["Andor", "Dark", "Test Entry 1", "The Wire"]);
+    app.sort_modes.insert(Status::Watching, SortMode::Progress);
+    assert_eq!(titles(&app), ["Andor", "The Wire", "Test Entry 1", "Dark"]);
+    app.sort_modes.insert(Status::Watching, SortMode::Remaining);
+    assert_eq!(titles(&app), ["Andor", "The Wire", "Test Entry 1", "Dark"]);
+
+    // Reordering is refused while sorted, and the manual order is kept for later.
+    app.selected_index = 3;
+    app.move_entry_down_in_column(1);
+    assert!(app.error.as_ref().unwrap().contains("manual"));
+    app.sort_modes.insert(Status::Watching, SortMode::Manual);
+    assert_eq!(titles(&app), ["Test Entry 1", "The Wire", "Andor", "Dark"]);
+}
+
+#[test]
+fn test_title_sort_key_ignores_articles() {
+    assert_eq!(title_sort_key("The Wire"), "wire");
+    assert_eq!(title_sort_key("An Idiot Abroad"), "idiot abroad");
+    assert_eq!(title_sort_key("The"), "the");
+    assert_eq!(title_sort_key("Theodosia"), "theodosia");
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::{press, temp_app, type_text};
+use crossterm::event::KeyCode;
+use senarai::action::Action;
+use senarai::app::{App, InputMode};
+use senarai::config::Config;
+use senarai::input::InputResult;
+use senarai::{command, database, Status};
+use std::fs;
+
+#[test]
+fn test_commands_update_the_selected_entry() {
+    let mut app = temp_app("commands-update");
+
+    press(&mut app, KeyCode::Char(':'));
+    assert_eq!(app.input_mode, InputMode::Command);
+    type_text(&mut app, "add breaking bad");
+    assert_eq!(press(&mut app, KeyCode::Enter), InputResult::Modified);
+    assert_eq!(app.input_mode, InputMode::Normal);
+    // Unlike the input bar, commands keep titles as typed.
+    assert_eq!(app.entry[0].title, "breaking bad");
+
+    app.dispatch(Action::RunCommand("set season 3".to_string()));
+    app.dispatch(Action::RunCommand("mo completed".to_string()));
+    let stored = database::load_entry(&app.config).unwrap();
+    assert_eq!(stored[0].season, 3);
+    assert_eq!(stored[0].status, Status::Completed);
+
+    app.dispatch(Action::RunCommand("move nowhere".to_string()));
+    assert!(app.error.as_ref().unwrap().contains("Unknown status"));
+    assert_eq!(
+        app.dispatch(Action::RunCommand("q".to_string())),
+        InputResult::Quit
+    );
+}
+
+#[test]
+fn test_drop_command_asks_first() {
+    let mut app = temp_app("commands-drop");
+    app.dispatch(Action::AddEntry("Lost".to_string()));
+
+    app.dispatch(Action::RunCommand("drop".to_string()));
+    assert_eq!(app.input_mode, InputMode::ConfirmDelete);
+    assert_eq!(app.entry[0].status, Status::Planning);
+    app.dispatch(Action::ConfirmRemove);
+    assert_eq!(app.entry[0].status, Status::Dropped);
+}
+
+#[test]
+fn test_next_command_counts_are_bounded() {
+    let app = App::new(Vec::new(), Config::default());
+    // A count is one action, saved once.
+    assert_eq!(
+        command::parse("next 999", &app).unwrap(),
+        vec![Action::NextEpisode(999)]
+    );
+    assert_eq!(command::parse("next 0", &app).unwrap(), Vec::new());
+    assert!(command::parse("next 1000", &app)
+        .unwrap_err()
+        .contains("999"));
+    assert_eq!(command::parse("quit", &app).unwrap(), vec![Action::Quit]);
+}
+
+#[test]
+fn test_export_command_picks_extension_from_format() {
+    let mut app = temp_app("commands-export");
+    app.dispatch(Action::AddEntry("Dark".to_string()));
+    let path = format!("{}/list", app.config.storage_path);
+
+    app.dispatch(Action::RunCommand(format!("export csv {}", path)));
+    assert!(app.error.is_none());
+    assert!(fs::read_to_string(format!("{}.csv", path))
+        .unwrap()
+        .contains("Dark"));
+
+    app.dispatch(Action::RunCommand(format!("export csv {}.json", path)));
+    assert!(app.error.as_ref().unwrap().contains("ends in .json"));
+}
+
+#[test]
+fn test_tab_completes_commands_statuses_and_titles() {
+    let mut app = temp_app("commands-complete");
+    app.dispatch(Action::AddEntry("The Office".to_string()));
+    app.dispatch(Action::AddEntry("The Wire".to_string()));
+
+    assert_eq!(command::complete("mo", &app), vec!["move"]);
+    assert_eq!(command::complete("move c", &app), vec!["move completed"]);
+    assert_eq!(
+        command::complete("goto the", &app),
+        vec!["goto The Office", "goto The Wire"]
+    );
+
+    press(&mut app, KeyCode::Char(':'));
+    type_text(&mut app, "go");
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.input, "goto ");
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.input, "goto The ");
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.input, "goto The Office");
+    press(&mut app, KeyCode::Tab);
+    assert_eq!(app.input, "goto The Wire");
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.entry[app.selected_index].title, "The Wire");
+}
+
+#[test]
+fn test_history_recalls_previous_commands() {
+    let mut app = temp_app("commands-history");
+    for line in ["help", "help", "dropped"] {
+        app.dispatch(Action::StartInput(InputMode::Command));
+        type_text(&mut app, line);
+        press(&mut app, KeyCode::Enter);
+        app.dispatch(Action::Cancel);
+    }
+    assert_eq!(app.command_history, vec!["help", "dropped"]);
+
+    app.dispatch(Action::StartInput(InputMode::Command));
+    type_text(&mut app, "dr");
+    press(&mut app, KeyCode::Up);
+    assert_eq!(app.input, "dropped");
+    press(&mut app, KeyCode::Up);
+    press(&mut app, KeyCode::Up);
+    assert_eq!(app.input, "help");
+    press(&mut app, KeyCode::Down);
+    press(&mut app, KeyCode::Down);
+    assert_eq!(app.input, "dr");
+}
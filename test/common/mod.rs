@@ -0,0 +1,60 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use senarai::action::Action;
+use senarai::app::App;
+use senarai::config::Config;
+use senarai::database;
+use senarai::input::{self, InputResult};
+use std::fs;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+/// An app storing its database in a fresh temp directory, removed again on drop.
+pub struct TempApp {
+    app: App,
+    dir: PathBuf,
+}
+
+impl Deref for TempApp {
+    type Target = App;
+
+    fn deref(&self) -> &App {
+        &self.app
+    }
+}
+
+impl DerefMut for TempApp {
+    fn deref_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+}
+
+impl Drop for TempApp {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+pub fn temp_app(name: &str) -> TempApp {
+    let dir = std::env::temp_dir().join(format!("senarai-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let config = Config {
+        storage_path: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    database::init_db(&config).unwrap();
+    TempApp {
+        app: App::new(Vec::new(), config),
+        dir,
+    }
+}
+
+pub fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        app.dispatch(Action::InsertChar(c));
+    }
+}
+
+pub fn press(app: &mut App, code: KeyCode) -> InputResult {
+    input::handle_key(KeyEvent::new(code, KeyModifiers::NONE), app)
+}
@@ -0,0 +1,202 @@
+use ratatui::style::Color;
+use senarai::config::{self, ConfigArgs};
+use senarai::keymap::Keymap;
+use senarai::theme::Theme;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_config(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("senarai-{}-{}.yaml", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_explicit_config_and_db_override() {
+    let path = temp_config("explicit", "storage_path: /tmp/from-config\n");
+    let mut args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    assert_eq!(config::find_config_file(&args), Ok(Some(path.clone())));
+    let config = config::load_config(&args).unwrap();
+    assert_eq!(config.storage_path, "/tmp/from-config");
+
+    args.db = Some(PathBuf::from("/tmp/from-flag"));
+    let config = config::load_config(&args).unwrap();
+    assert_eq!(config.storage_path, "/tmp/from-flag");
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_missing_explicit_config_is_an_error() {
+    let args = ConfigArgs {
+        config: Some(PathBuf::from("/nonexistent/senarai.yaml")),
+        db: None,
+    };
+    assert!(config::load_config(&args).is_err());
+}
+
+#[test]
+fn test_storage_path_is_optional() {
+    let path = temp_config("optional", "csv:\n  title: Name\n");
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    let config = config::load_config(&args).unwrap();
+    assert_eq!(config.storage_path, config::default_storage_path());
+    assert_eq!(config.csv.title, "Name");
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_conflicting_keys_keep_the_rest_of_the_config() {
+    let path = temp_config(
+        "conflict",
+        "storage_path: /tmp/from-config
+keys:
+  dropped: e
+",
+    );
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    let config = config::load_config(&args).unwrap();
+    assert_eq!(config.storage_path, "/tmp/from-config");
+    assert!(Keymap::new(&config.keys).is_err());
+
+    let (_, diagnostics) = config::check_config(&args).unwrap();
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.line == Some(3) && d.message.contains("'dropped'")));
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_check_reports_unknown_keys_and_bad_values_with_lines() {
+    let storage = std::env::temp_dir();
+    let path = temp_config(
+        "check",
+        &format!(
+            "storage_path: {}\nimport:\n  on_hold_status: Paused\n  status_synonyms:\n    backlog: Planning\ncsv:\n  titel: Name\n",
+            storage.display()
+        ),
+    );
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    let (checked, diagnostics) = config::check_config(&args).unwrap();
+    assert_eq!(checked, Some(path.clone()));
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].line, Some(3));
+    assert!(diagnostics[0].message.contains("Paused"));
+    assert_eq!(diagnostics[1].line, Some(7));
+    assert!(diagnostics[1].message.contains("did you mean 'csv.title'"));
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_default_config_is_valid_and_show_reports_sources() {
+    let path = temp_config("default", config::DEFAULT_CONFIG);
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: Some(std::env::temp_dir()),
+    };
+
+    let (_, diagnostics) = config::check_config(&args).unwrap();
+    assert_eq!(diagnostics, Vec::new());
+
+    let settings = config::describe_config(&args).unwrap();
+    let source = |key: &str| {
+        settings
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, _, source)| source.clone())
+            .unwrap()
+    };
+    assert_eq!(source("storage_path"), "--db");
+    assert_eq!(source("csv.title"), path.display().to_string());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_every_default_setting_is_a_known_key() {
+    // A file with every setting spelled out, as `config show` lists them.
+    let data = serde_yaml::to_string(&config::Config::default()).unwrap();
+    let path = temp_config("serialized", &data);
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: Some(std::env::temp_dir()),
+    };
+
+    let (_, diagnostics) = config::check_config(&args).unwrap();
+    assert_eq!(diagnostics, Vec::new());
+    let settings = config::describe_config(&args).unwrap();
+    assert!(settings
+        .iter()
+        .any(|(key, _, _)| key == "import.on_hold_status"));
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_theme_name_and_color_overrides() {
+    let path = temp_config(
+        "theme",
+        "theme:\n  name: light\n  highlight_bg: \"#268bd2\"\n  highlight_fg: 15\n  title: light-yellow\n",
+    );
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    let theme = config::load_config(&args).unwrap().theme.resolve();
+    assert_eq!(theme.highlight_bg, Color::Rgb(0x26, 0x8b, 0xd2));
+    assert_eq!(theme.highlight_fg, Color::Indexed(15));
+    assert_eq!(theme.title, Color::LightYellow);
+    assert_eq!(theme.text, Theme::LIGHT.text);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_unknown_theme_color_is_rejected() {
+    let path = temp_config(
+        "bad-theme",
+        "theme:\n  name: high-contrast\n  border: blurple\n",
+    );
+    let args = ConfigArgs {
+        config: Some(path.clone()),
+        db: None,
+    };
+
+    let error = match config::load_config(&args) {
+        Ok(_) => panic!("invalid theme color was accepted"),
+        Err(error) => error,
+    };
+    assert!(error.contains("blurple"), "{}", error);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_flat_theme_colors_skip_the_gradient() {
+    assert_eq!(
+        Theme::HIGH_CONTRAST.progress_color(3, 10),
+        Color::LightGreen
+    );
+    assert_eq!(Theme::DARK.progress_color(0, 10), Color::Rgb(90, 145, 220));
+    assert_ne!(Theme::DARK.progress_color(9, 10), Color::Rgb(90, 145, 220));
+}
@@ -0,0 +1,409 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use senarai::formats::csv::{self, CsvMapping};
+use senarai::formats::ics;
+use senarai::formats::report::{self, ReportFormat};
+use senarai::formats::{json, letterboxd, mal, parse_status, trakt, tvtime};
+use senarai::import::{self, ImportMode, ImportOptions, ParsedImport};
+use senarai::{Entry, Status};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn entry(title: &str, status: Status) -> Entry {
+    Entry {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        season: 1,
+        status,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_json_round_trip_keeps_ordering() {
+    let entries = vec![
+        entry("First", Status::Planning),
+        entry("Second", Status::Watching),
+    ];
+
+    let data = json::to_string(&entries).unwrap();
+    let imported = json::from_str(&data).unwrap();
+    assert_eq!(imported, entries);
+}
+
+#[test]
+fn test_json_rejects_newer_schema() {
+    let data = r#"{"schema_version": 99, "exported_at": "2026-01-01T00:00:00Z", "entries": []}"#;
+    assert!(json::from_str(data).is_err());
+}
+
+#[test]
+fn test_merge_updates_by_id_and_skips_duplicate_titles() {
+    let existing = vec![
+        entry("Kept", Status::Planning),
+        entry("Changed", Status::Planning),
+    ];
+
+    let mut changed = existing[1].clone();
+    changed.status = Status::Watching;
+    let incoming = vec![
+        changed,
+        entry("New", Status::Completed),
+        entry("Kept", Status::Watching),
+    ];
+
+    let plan = import::plan(&existing, incoming.into(), ImportMode::Merge);
+    assert_eq!(plan.report.updated, vec!["Changed"]);
+    assert_eq!(plan.report.added, vec!["New"]);
+    assert_eq!(plan.report.skipped.len(), 1);
+    assert!(plan.report.removed.is_empty());
+    assert_eq!(plan.entries.len(), 3);
+    assert_eq!(plan.entries[1].status, Status::Watching);
+}
+
+#[test]
+fn test_replace_reports_removed_entries() {
+    let existing = vec![
+        entry("Old", Status::Planning),
+        entry("Same", Status::Watching),
+    ];
+    let incoming = vec![existing[1].clone(), entry("New", Status::Planning)];
+
+    let plan = import::plan(&existing, incoming.into(), ImportMode::Replace);
+    assert_eq!(plan.report.removed, vec!["Old"]);
+    assert_eq!(plan.report.added, vec!["New"]);
+    assert_eq!(plan.report.unchanged, 1);
+    assert_eq!(plan.entries.len(), 2);
+}
+
+#[test]
+fn test_parse_status_is_lenient() {
+    let options = ImportOptions {
+        status_synonyms: HashMap::from([("Backlog".to_string(), Status::Planning)]),
+        on_hold_status: Status::Watching,
+    };
+
+    assert_eq!(
+        parse_status("Plan to Watch", &options),
+        Some(Status::Planning)
+    );
+    assert_eq!(parse_status("WATCHING", &options), Some(Status::Watching));
+    assert_eq!(parse_status("on_hold", &options), Some(Status::Watching));
+    assert_eq!(parse_status("backlog", &options), Some(Status::Planning));
+    assert_eq!(parse_status("sleeping", &options), None);
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+    let mut first = entry("First, with comma", Status::Watching);
+    first.runtime_minutes = 24;
+    first.season_runtimes.insert(2, 45);
+    first.add_tag("anime");
+    first.add_tag("rewatch");
+    first.log_watched("2026-10-18".parse().unwrap(), 2);
+    first.log_watched("2026-10-19".parse().unwrap(), 1);
+    first.updated_at = Some(at("2026-10-19 21:05:30"));
+    let entries = vec![first, entry("Second", Status::Completed)];
+
+    let data = csv::to_string(&entries).unwrap();
+    let parsed = csv::from_str(&data, &CsvMapping::default(), &ImportOptions::default()).unwrap();
+    assert!(parsed.rejected.is_empty());
+    assert_eq!(parsed.entries[0].tags, ["anime", "rewatch"]);
+    assert_eq!(parsed.entries[0].watch_log.values().sum::<u32>(), 3);
+    assert!(data.contains(",2026-10-19 21:05:30,"));
+    assert_eq!(parsed.entries, entries);
+}
+
+#[test]
+fn test_csv_column_mapping_and_rejected_rows() {
+    let data = "Name,State,Seen,Total\n\
+                Frieren,Currently Watching,10,28\n\
+                Monster,On Hold,30,74\n\
+                Broken,Sleeping,1,1\n\
+                ,Completed,1,1\n";
+
+    let mut mapping = CsvMapping::default();
+    for assignment in ["title=Name", "status=State", "watched=Seen", "max=Total"] {
+        mapping.set_column(assignment).unwrap();
+    }
+    assert!(mapping.set_column("rating=Score").is_err());
+    let options = ImportOptions {
+        on_hold_status: Status::Watching,
+        ..Default::default()
+    };
+
+    let parsed = csv::from_str(data, &mapping, &options).unwrap();
+    assert_eq!(parsed.entries.len(), 2);
+    assert_eq!(parsed.entries[0].watched_episodes, 10);
+    assert_eq!(parsed.entries[0].max_episodes, 28);
+    assert_eq!(parsed.entries[1].status, Status::Watching);
+    assert_eq!(parsed.rejected.len(), 2);
+    assert_eq!(parsed.rejected[0].0, "row 4");
+}
+
+#[test]
+fn test_rejected_rows_and_duplicates_are_reported_as_skipped() {
+    let existing = vec![entry("Frieren", Status::Watching)];
+    let parsed = ParsedImport {
+        entries: vec![
+            entry("Frieren", Status::Planning),
+            entry("New", Status::Planning),
+        ],
+        rejected: vec![("row 3".to_string(), "missing title".to_string())],
+        ..Default::default()
+    };
+
+    let plan = import::plan(&existing, parsed, ImportMode::Merge);
+    assert_eq!(plan.report.added, vec!["New"]);
+    assert_eq!(plan.report.skipped.len(), 2);
+}
+
+#[test]
+fn test_mal_import_maps_statuses_and_episodes() {
+    let data = r#"<?xml version="1.0" encoding="UTF-8" ?>
+<myanimelist>
+    <myinfo><user_export_type>1</user_export_type></myinfo>
+    <anime>
+        <series_title><![CDATA[Cowboy Bebop]]></series_title>
+        <series_episodes>26</series_episodes>
+        <my_watched_episodes>26</my_watched_episodes>
+        <my_score>9</my_score>
+        <my_status>Completed</my_status>
+    </anime>
+    <anime>
+        <series_title>Tom &amp; Jerry</series_title>
+        <series_episodes>12</series_episodes>
+        <my_watched_episodes>3</my_watched_episodes>
+        <my_status>On-Hold</my_status>
+    </anime>
+    <anime>
+        <series_title>Broken</series_title>
+        <my_watched_episodes>many</my_watched_episodes>
+        <my_status>Plan to Watch</my_status>
+    </anime>
+</myanimelist>"#;
+    let options = ImportOptions {
+        on_hold_status: Status::Watching,
+        ..Default::default()
+    };
+
+    let parsed = mal::from_str(data, &options).unwrap();
+    assert_eq!(parsed.entries.len(), 2);
+    assert_eq!(parsed.entries[0].title, "Cowboy Bebop");
+    assert_eq!(parsed.entries[0].status, Status::Completed);
+    assert_eq!(parsed.entries[0].max_episodes, 26);
+    assert_eq!(parsed.entries[1].title, "Tom & Jerry");
+    assert_eq!(parsed.entries[1].status, Status::Watching);
+    assert_eq!(parsed.entries[1].watched_episodes, 3);
+    assert_eq!(parsed.rejected[0].0, "Broken");
+}
+
+#[test]
+fn test_mal_round_trip() {
+    let mut watching = entry("Frieren & Friends", Status::Watching);
+    watching.watched_episodes = 10;
+    watching.max_episodes = 28;
+    let entries = vec![watching, entry("Mushishi", Status::Planning)];
+
+    let parsed = mal::from_str(&mal::to_string(&entries), &ImportOptions::default()).unwrap();
+    assert_eq!(parsed.entries.len(), 2);
+    assert_eq!(parsed.entries[0].title, "Frieren & Friends");
+    assert_eq!(parsed.entries[0].watched_episodes, 10);
+    assert_eq!(parsed.entries[0].max_episodes, 28);
+    assert_eq!(parsed.entries[1].status, Status::Planning);
+
+    let data = mal::to_string(&entries);
+    assert!(!data.contains("my_score") && !data.contains("0000-00-00"));
+}
+
+#[test]
+fn test_mal_reimport_updates_existing_titles() {
+    let mut watching = entry("Frieren", Status::Watching);
+    watching.episode = 10;
+    watching.watched_episodes = 10;
+    watching.max_episodes = 28;
+    let existing = vec![watching, entry("Mushishi", Status::Planning)];
+    let data = mal::to_string(&existing);
+
+    let parsed = mal::from_str(&data, &ImportOptions::default()).unwrap();
+    let plan = import::plan(&existing, parsed, ImportMode::Merge);
+    assert!(plan.report.added.is_empty());
+    assert!(plan.report.skipped.is_empty());
+    assert_eq!(plan.entries, existing);
+
+    let progressed = data.replace(
+        "<my_watched_episodes>10</my_watched_episodes>",
+        "<my_watched_episodes>12</my_watched_episodes>",
+    );
+    let parsed = mal::from_str(&progressed, &ImportOptions::default()).unwrap();
+    let plan = import::plan(&existing, parsed, ImportMode::Merge);
+    assert_eq!(plan.report.updated, vec!["Frieren"]);
+    assert_eq!(plan.entries.len(), 2);
+    assert_eq!(plan.entries[0].id, existing[0].id);
+    assert_eq!(plan.entries[0].watched_episodes, 12);
+}
+
+#[test]
+fn test_trakt_import_tallies_episodes() {
+    let data = r#"[
+        {"plays": 4, "show": {"title": "Dark"}, "seasons": [
+            {"number": 0, "episodes": [{"number": 1}]},
+            {"number": 1, "episodes": [{"number": 1}, {"number": 2}]},
+            {"number": 2, "episodes": [{"number": 1}]}
+        ]},
+        {"plays": 1, "movie": {"title": "Heat"}},
+        {"listed_at": "2024-01-01T00:00:00.000Z", "type": "show", "show": {"title": "Andor"}},
+        {"listed_at": "2024-01-01T00:00:00.000Z"}
+    ]"#;
+
+    let parsed = trakt::from_str(data).unwrap();
+    assert!(parsed.match_titles);
+    assert_eq!(parsed.rejected.len(), 1);
+
+    let heat = &parsed.entries[0];
+    assert_eq!(heat.status, Status::Completed);
+    assert_eq!(heat.max_episodes, 1);
+    assert_eq!(parsed.entries[1].title, "Andor");
+    assert_eq!(parsed.entries[1].status, Status::Planning);
+
+    let dark = &parsed.entries[2];
+    assert_eq!(dark.status, Status::Watching);
+    assert_eq!((dark.season, dark.episode), (2, 1));
+    assert_eq!(dark.watched_episodes, 3);
+}
+
+#[test]
+fn test_letterboxd_import_dedupes_rewatches() {
+    let data = "Date,Name,Year,Letterboxd URI\n\
+                2024-01-01,Heat,1995,https://boxd.it/a\n\
+                2024-02-01,Heat,1995,https://boxd.it/a\n\
+                2024-03-01,,1979,https://boxd.it/b\n";
+
+    let watched = letterboxd::from_str(data, false).unwrap();
+    assert_eq!(watched.entries.len(), 1);
+    assert_eq!(watched.entries[0].status, Status::Completed);
+    assert_eq!(watched.rejected.len(), 1);
+
+    let watchlist = letterboxd::from_str(data, true).unwrap();
+    assert_eq!(watchlist.entries[0].status, Status::Planning);
+}
+
+#[test]
+fn test_tvtime_import_tallies_episodes() {
+    let data = "tv_show_name,episode_season_number,episode_number\n\
+                Dark,1,1\n\
+                Dark,1,2\n\
+                Dark,x,3\n";
+
+    let parsed = tvtime::from_str(data).unwrap();
+    assert_eq!(parsed.entries.len(), 1);
+    assert_eq!(parsed.entries[0].watched_episodes, 2);
+    assert_eq!(
+        (parsed.entries[0].season, parsed.entries[0].episode),
+        (1, 2)
+    );
+    assert_eq!(parsed.rejected.len(), 1);
+}
+
+#[test]
+fn test_merge_by_title_updates_progress() {
+    let mut existing = entry("Dark", Status::Planning);
+    existing.max_episodes = 26;
+    existing.runtime_minutes = 50;
+    let id = existing.id;
+
+    let mut incoming = entry("Dark", Status::Watching);
+    incoming.episode = 4;
+    incoming.watched_episodes = 4;
+    let parsed = ParsedImport {
+        entries: vec![incoming],
+        match_titles: true,
+        ..Default::default()
+    };
+
+    let plan = import::plan(&[existing], parsed, ImportMode::Merge);
+    assert_eq!(plan.report.updated, vec!["Dark"]);
+    let dark = &plan.entries[0];
+    assert_eq!(dark.id, id);
+    assert_eq!(dark.status, Status::Watching);
+    assert_eq!(dark.watched_episodes, 4);
+    assert_eq!(dark.max_episodes, 26);
+    assert_eq!(dark.runtime_minutes, 50);
+}
+
+#[test]
+fn test_markdown_report_groups_by_status() {
+    let mut watching = entry("Tom | Jerry", Status::Watching);
+    watching.watched_episodes = 3;
+    watching.max_episodes = 12;
+    let entries = vec![
+        watching,
+        entry("Later", Status::Planning),
+        entry("Gave Up", Status::Dropped),
+    ];
+
+    let out = report::render(&entries, ReportFormat::Markdown, None);
+    assert!(out.contains("## Watching (1)"));
+    assert!(out.contains("| Tom \\| Jerry | 1 | 0 | 3/12 (25%) |"));
+    assert!(out.contains("## Completed (0)"));
+    assert!(out.find("## Dropped (1)").unwrap() > out.find("## Completed").unwrap());
+
+    let out = report::render(&entries[..2], ReportFormat::Markdown, None);
+    assert!(!out.contains("Dropped ("));
+
+    // Placeholders inside titles are inserted as written, not filled in again.
+    let entries = vec![entry("The {{dropped}} Show", Status::Watching)];
+    let out = report::render(
+        &entries,
+        ReportFormat::Markdown,
+        Some("{{watching}}{{unknown}}"),
+    );
+    assert!(out.contains("| The {{dropped}} Show |"));
+    assert!(out.ends_with("{{unknown}}"));
+}
+
+#[test]
+fn test_html_report_escapes_and_uses_template() {
+    let entries = vec![entry("<b>Bold</b>", Status::Completed)];
+
+    let out = report::render(&entries, ReportFormat::Html, None);
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.contains("&lt;b&gt;Bold&lt;/b&gt;"));
+    assert!(!out.contains("{{"));
+
+    let out = report::render(
+        &entries,
+        ReportFormat::Html,
+        Some("<div>{{completed}}</div>"),
+    );
+    assert!(out.starts_with("<div><section class=\"completed\">"));
+}
+
+#[test]
+fn test_ics_lists_upcoming_episodes_and_sessions() {
+    let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+    let mut watching = entry("Dark, Season 2", Status::Watching);
+    watching.episode = 3;
+    watching.watched_episodes = 3;
+    watching.max_episodes = 6;
+    watching.runtime_minutes = 50;
+    watching.next_air_date = NaiveDate::from_ymd_opt(2026, 3, 2);
+    watching.watch_sessions = vec![at("2026-03-01 20:00"), at("2026-03-10 21:30")];
+    let mut planning = entry("Later", Status::Planning);
+    planning.next_air_date = NaiveDate::from_ymd_opt(2026, 3, 2);
+
+    let out = ics::to_string(&[watching.clone(), planning], at("2026-03-05 12:00"));
+    assert!(out.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(out.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(out.matches("BEGIN:VEVENT").count(), 3);
+    // S1E4 aired on March 2nd; E5 and E6 follow weekly.
+    assert!(!out.contains(&format!("UID:{}-s1e4@senarai", watching.id)));
+    assert!(out.contains(&format!("UID:{}-s1e5@senarai", watching.id)));
+    assert!(out.contains("DTSTART;VALUE=DATE:20260316"));
+    assert!(out.contains("SUMMARY:Dark\\, Season 2 S1E6"));
+    assert!(out.contains(&format!(
+        "UID:{}-session-20260310T2130@senarai",
+        watching.id
+    )));
+    assert!(out.contains("DURATION:PT50M"));
+}
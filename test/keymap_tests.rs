@@ -0,0 +1,180 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use senarai::keymap::{KeyAction, KeyChord, KeyConfig, KeyContext, KeyMatch, Keymap};
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+fn parse_keys(yaml: &str) -> KeyConfig {
+    serde_yaml::from_str(yaml).unwrap()
+}
+
+#[test]
+fn test_parse_key_chords() {
+    let chord = |text: &str| text.parse::<KeyChord>().unwrap();
+
+    assert_eq!(chord("q").code, KeyCode::Char('q'));
+    assert_eq!(chord("+").code, KeyCode::Char('+'));
+    assert_eq!(chord("space").code, KeyCode::Char(' '));
+    assert_eq!(chord("shift+x"), chord("X"));
+    assert_eq!(
+        chord("Ctrl+d"),
+        KeyChord {
+            code: KeyCode::Char('d'),
+            modifiers: KeyModifiers::CONTROL
+        }
+    );
+    assert_eq!(
+        chord("ctrl++"),
+        KeyChord {
+            code: KeyCode::Char('+'),
+            modifiers: KeyModifiers::CONTROL
+        }
+    );
+    assert_eq!(chord("shift+up").to_string(), "shift+up");
+    assert_eq!(chord("pgdn").to_string(), "pagedown");
+    assert!("hyper+q".parse::<KeyChord>().is_err());
+    assert!("f13".parse::<KeyChord>().is_err());
+}
+
+#[test]
+fn test_default_keymap_matches_events() {
+    let keymap = Keymap::default();
+
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('#'), KeyModifiers::SHIFT)
+        ),
+        Some(KeyAction::NextSeason)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Episodes,
+            key(KeyCode::Char('#'), KeyModifiers::NONE)
+        ),
+        Some(KeyAction::SetMaxEpisodes)
+    );
+    assert_eq!(
+        keymap.action(KeyContext::Normal, key(KeyCode::Up, KeyModifiers::SHIFT)),
+        Some(KeyAction::MoveUp)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Dropped,
+            key(KeyCode::Char('X'), KeyModifiers::SHIFT)
+        ),
+        Some(KeyAction::RemoveAllDropped)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Dropped,
+            key(KeyCode::Char('a'), KeyModifiers::NONE)
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_overrides_replace_default_keys() {
+    let keys = parse_keys("next_season: [ctrl+s, n]\nquit: ctrl+c\n");
+    let keymap = Keymap::new(&keys).unwrap();
+
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('#'), KeyModifiers::NONE)
+        ),
+        None
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        ),
+        Some(KeyAction::NextSeason)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Dropped,
+            key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        ),
+        Some(KeyAction::Quit)
+    );
+    assert_eq!(keymap.describe(KeyAction::NextSeason), "ctrl+s/n");
+    assert!(keymap
+        .help_lines(KeyContext::Normal)
+        .contains(&"ctrl+s/n: increase season".to_string()));
+}
+
+#[test]
+fn test_conflicts_are_detected_per_context() {
+    let conflict = Keymap::new(&parse_keys("dropped: e\n")).unwrap_err();
+    assert_eq!(conflict.context, KeyContext::Normal);
+    assert_eq!(conflict.actions, (KeyAction::Edit, KeyAction::Dropped));
+
+    // `r` reactivates in the dropped list, which has no runtime action.
+    assert!(Keymap::new(&parse_keys("reactivate: R\n")).is_ok());
+}
+
+#[test]
+fn test_unknown_action_suggests_a_name() {
+    let error = serde_yaml::from_str::<KeyConfig>("nxt_season: n\n").unwrap_err();
+    assert!(
+        error.to_string().contains("did you mean 'next_season'"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_vim_profile_and_key_sequences() {
+    let keymap = Keymap::new(&parse_keys("profile: vim\n")).unwrap();
+    let g = "g".parse::<KeyChord>().unwrap();
+
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('j'), KeyModifiers::NONE)
+        ),
+        Some(KeyAction::Down)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('?'), KeyModifiers::NONE)
+        ),
+        Some(KeyAction::Help)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Normal,
+            key(KeyCode::Char('d'), KeyModifiers::CONTROL)
+        ),
+        Some(KeyAction::HalfPageDown)
+    );
+    assert_eq!(keymap.lookup(KeyContext::Normal, &[g]), KeyMatch::Pending);
+    assert_eq!(
+        keymap.lookup(KeyContext::Normal, &[g, g]),
+        KeyMatch::Action(KeyAction::First)
+    );
+    assert_eq!(
+        keymap.lookup(KeyContext::Dropped, &[g, g]),
+        KeyMatch::Action(KeyAction::First)
+    );
+    assert_eq!(
+        keymap.action(
+            KeyContext::Dropped,
+            key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        ),
+        Some(KeyAction::PageDown)
+    );
+    assert_eq!(keymap.describe(KeyAction::First), "g g/home");
+}
+
+#[test]
+fn test_binding_that_starts_another_conflicts() {
+    let conflict = Keymap::new(&parse_keys("profile: vim\nadd: g\n")).unwrap_err();
+    assert_eq!(conflict.actions, (KeyAction::Add, KeyAction::First));
+    assert_eq!(conflict.binding.to_string(), "g");
+}
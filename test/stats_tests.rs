@@ -0,0 +1,123 @@
+use chrono::NaiveDate;
+use senarai::stats::{
+    current_streak, episodes_per_day, longest_streak, watched_on, week_start, Stats, WEEKS,
+};
+use senarai::{Entry, Status};
+
+fn date(s: &str) -> NaiveDate {
+    s.parse().unwrap()
+}
+
+fn entry(title: &str, status: Status, watched: u32, max: u32) -> Entry {
+    Entry {
+        title: title.to_string(),
+        status,
+        watched_episodes: watched,
+        max_episodes: max,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_stats_summarize_the_watchlist() {
+    let entries = vec![
+        entry("Severance", Status::Watching, 3, 10),
+        entry("The Bear", Status::Watching, 8, 10),
+        entry("Unknown Length", Status::Watching, 4, 0),
+        entry("Dark", Status::Completed, 26, 26),
+        entry("Lost", Status::Dropped, 20, 121),
+        entry("Shogun", Status::Planning, 0, 10),
+    ];
+    let stats = Stats::new(&entries, date("2026-10-19"));
+
+    assert_eq!(stats.entries, 6);
+    let counts: Vec<(Status, usize)> = stats
+        .per_status
+        .iter()
+        .map(|c| (c.status, c.entries))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            (Status::Planning, 1),
+            (Status::Watching, 3),
+            (Status::Completed, 1),
+            (Status::Dropped, 1),
+        ]
+    );
+    assert_eq!(stats.episodes_watched, 61);
+    // One of the five started entries is completed; Planning doesn't count.
+    assert_eq!(stats.completion_rate, Some(20));
+    // Entries without a known episode count are left out of the average.
+    assert_eq!(stats.watching_progress, Some(55));
+
+    let longest: Vec<&str> = stats
+        .longest_running
+        .iter()
+        .map(|s| s.title.as_str())
+        .collect();
+    assert_eq!(longest, ["Lost", "Dark", "Severance", "Shogun", "The Bear"]);
+    assert!(stats.episodes_per_week.is_empty());
+
+    let empty = Stats::new(&[], date("2026-10-19"));
+    assert_eq!(empty.completion_rate, None);
+    assert_eq!(empty.watching_progress, None);
+}
+
+#[test]
+fn test_episodes_per_week_come_from_the_watch_log() {
+    let today = date("2026-10-21");
+    let mut first = entry("Severance", Status::Watching, 0, 0);
+    first.watch_log.insert(date("2026-10-19"), 2);
+    first.watch_log.insert(date("2026-10-18"), 1);
+    let mut second = entry("The Bear", Status::Watching, 0, 0);
+    second.watch_log.insert(date("2026-10-21"), 3);
+    // Too old for the chart.
+    second.watch_log.insert(date("2025-01-01"), 9);
+
+    let stats = Stats::new(&[first, second], today);
+    assert_eq!(week_start(today), date("2026-10-19"));
+    assert_eq!(stats.episodes_per_week.len(), WEEKS);
+
+    let last = &stats.episodes_per_week[WEEKS - 1];
+    assert_eq!((last.week, last.episodes), (date("2026-10-19"), 5));
+    let previous = &stats.episodes_per_week[WEEKS - 2];
+    assert_eq!((previous.week, previous.episodes), (date("2026-10-12"), 1));
+    let total: u32 = stats.episodes_per_week.iter().map(|w| w.episodes).sum();
+    assert_eq!(total, 6);
+}
+
+#[test]
+fn test_streaks_count_days_in_a_row() {
+    let mut severance = entry("Severance", Status::Watching, 0, 0);
+    for day in [
+        "2026-10-01",
+        "2026-10-02",
+        "2026-10-03",
+        "2026-10-17",
+        "2026-10-18",
+    ] {
+        severance.log_watched(date(day), 1);
+    }
+    let mut bear = entry("The Bear", Status::Watching, 0, 0);
+    bear.log_watched(date("2026-10-04"), 1);
+    bear.log_watched(date("2026-10-18"), 2);
+    let entries = [severance, bear];
+
+    let days = episodes_per_day(&entries);
+    assert_eq!(days.get(&date("2026-10-18")), Some(&3));
+    assert_eq!(longest_streak(&days), 4);
+    // Today doesn't break the streak before it is over, but a missed day does.
+    assert_eq!(current_streak(&days, date("2026-10-19")), 2);
+    assert_eq!(current_streak(&days, date("2026-10-20")), 0);
+
+    let watched = watched_on(&entries, date("2026-10-18"));
+    let titles: Vec<(&str, u32)> = watched
+        .iter()
+        .map(|s| (s.title.as_str(), s.episodes))
+        .collect();
+    assert_eq!(titles, [("The Bear", 2), ("Severance", 1)]);
+
+    let stats = Stats::new(&entries, date("2026-10-18"));
+    assert_eq!((stats.current_streak, stats.longest_streak), (2, 4));
+}
@@ -0,0 +1,129 @@
+use ratatui::{backend::TestBackend, Terminal};
+use senarai::action::Action;
+use senarai::app::App;
+use senarai::config::Config;
+use senarai::{consts, scroll, ui, Entry, Status};
+
+fn app_with(statuses: &[Status]) -> App {
+    let entries = statuses
+        .iter()
+        .enumerate()
+        .map(|(i, status)| Entry {
+            title: format!("Show {}", i),
+            status: *status,
+            ..Default::default()
+        })
+        .collect();
+    App::new(entries, Config::default())
+}
+
+fn draw(app: &mut App, width: u16, height: u16) {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| ui::draw_ui(f, app)).unwrap();
+}
+
+/// Where `text` starts on the drawn screen, as (column, row).
+fn find_text(terminal: &Terminal<TestBackend>, text: &str) -> Option<(u16, u16)> {
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height).find_map(|y| {
+        let row: String = (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect();
+        row.find(text).map(|x| (x as u16, y))
+    })
+}
+
+fn title_at(app: &mut App, x: u16, y: u16) -> Option<String> {
+    app.mouse_pos = (x, y);
+    ui::get_mouse_selection(app).map(|i| app.entry[i].title.clone())
+}
+
+#[test]
+fn test_mouse_hits_entries_in_wide_and_tabbed_layouts() {
+    let mut app = app_with(&[Status::Planning, Status::Watching, Status::Watching]);
+
+    draw(&mut app, 120, 30);
+    assert!(app.tab_layout.is_empty());
+    // Rows start below each column's border; the Watching column starts at x = 40.
+    assert_eq!(title_at(&mut app, 45, 2).as_deref(), Some("Show 2"));
+    assert_eq!(title_at(&mut app, 5, 1).as_deref(), Some("Show 0"));
+    assert_eq!(title_at(&mut app, 5, 2), None);
+
+    app.selected_index = 1;
+    draw(&mut app, 60, 30);
+    assert_eq!(app.tab_layout.len(), 3);
+    // Only the Watching column is shown, below the tabs, across the whole width.
+    assert_eq!(title_at(&mut app, 5, 2).as_deref(), Some("Show 1"));
+    assert_eq!(title_at(&mut app, 50, 3).as_deref(), Some("Show 2"));
+    app.mouse_pos = (app.tab_layout[2].x, 0);
+    assert_eq!(ui::get_mouse_tab(&app), Some(Status::Completed));
+    assert_eq!(ui::get_mouse_column(&app), Some(Status::Completed));
+}
+
+#[test]
+fn test_tiny_terminal_draws_nothing_clickable() {
+    let mut app = app_with(&[Status::Planning]);
+    draw(&mut app, 20, 5);
+    assert!(app.column_layout.is_empty());
+    assert_eq!(title_at(&mut app, 2, 1), None);
+    assert_eq!(ui::get_mouse_column(&app), None);
+}
+
+#[test]
+fn test_scroll_offset_keeps_context_and_stays_put() {
+    // 30 rows shown 9 at a time, with two rows of context.
+    assert_eq!(scroll::offset_for(0, Some(6), 30, 9), 0);
+    assert_eq!(scroll::offset_for(0, Some(7), 30, 9), 1);
+    // Moving back up inside the view leaves it where it is.
+    assert_eq!(scroll::offset_for(5, Some(8), 30, 9), 5);
+    assert_eq!(scroll::offset_for(5, Some(6), 30, 9), 4);
+    // Never past the end, and the first rows need no context above them.
+    assert_eq!(scroll::offset_for(40, Some(29), 30, 9), 21);
+    assert_eq!(scroll::offset_for(3, Some(0), 30, 9), 0);
+
+    assert_eq!(scroll::selection_in_view(2, 10, 30, 9), 12);
+    assert_eq!(scroll::selection_in_view(25, 10, 30, 9), 16);
+    assert_eq!(scroll::selection_in_view(29, 21, 30, 9), 29);
+}
+
+#[test]
+fn test_columns_keep_their_scroll_position() {
+    let mut app = app_with(&[Status::Completed; 30]);
+    app.selected_index = 9;
+    draw(&mut app, 90, 12);
+    assert_eq!(app.column_offsets[&Status::Completed], 3);
+    // The first visible row is Show 3, so that is what a click there hits.
+    assert_eq!(title_at(&mut app, 70, 1).as_deref(), Some("Show 3"));
+
+    app.dispatch(Action::SelectPrev);
+    draw(&mut app, 90, 12);
+    assert_eq!(app.column_offsets[&Status::Completed], 3);
+
+    app.dispatch(Action::ScrollColumn(Status::Completed, 10));
+    assert_eq!(app.column_offsets[&Status::Completed], 13);
+    assert_eq!(app.entry[app.selected_index].title, "Show 15");
+}
+
+#[test]
+fn test_detail_pane_sits_beside_wide_boards_and_below_narrow_ones() {
+    let mut app = app_with(&[Status::Planning, Status::Watching]);
+    app.dispatch(Action::ToggleDetails);
+
+    let width = consts::DETAIL_PANE_SIDE_MIN_WIDTH;
+    let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+    terminal.draw(|f| ui::draw_ui(f, &mut app)).unwrap();
+    let (x, y) = find_text(&terminal, "Details").unwrap();
+    assert!(x >= width - consts::DETAIL_PANE_WIDTH);
+    assert_eq!(y, 0);
+    assert!(app
+        .column_layout
+        .iter()
+        .all(|c| c.right() <= width - consts::DETAIL_PANE_WIDTH));
+
+    let mut terminal = Terminal::new(TestBackend::new(width - 1, 30)).unwrap();
+    terminal.draw(|f| ui::draw_ui(f, &mut app)).unwrap();
+    let (x, y) = find_text(&terminal, "Details").unwrap();
+    assert!(x < consts::DETAIL_PANE_WIDTH);
+    assert!(y > 0);
+    assert!(app.column_layout.iter().all(|c| c.bottom() <= y));
+}